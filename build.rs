@@ -1,10 +1,12 @@
 fn main() {
     const IME_ICON_INDEX_AVRO: &str = "11";
     const IME_ICON_INDEX_KHIPRO: &str = "12";
+    const IME_ICON_INDEX_ENGLISH: &str = "13";
 
     winres::WindowsResource::new()
         .set_icon_with_id("resources/Avro.ico", IME_ICON_INDEX_AVRO)
         .set_icon_with_id("resources/Khipro.ico", IME_ICON_INDEX_KHIPRO)
+        .set_icon_with_id("resources/IME.ico", IME_ICON_INDEX_ENGLISH)
         .compile()
         .unwrap();
 }
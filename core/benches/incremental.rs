@@ -0,0 +1,45 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Compares the cost of a keystroke with full and incremental conversion as the input grows.
+//!
+//! Run with `cargo bench -p uo-keyboard-core`.
+
+use std::{hint::black_box, time::Instant};
+
+use okkhor::parser::Parser;
+use uo_keyboard_core::incremental::IncrementalConverter;
+
+const SENTENCE: &str = "ami banglay gan gai amar sOnar bangla ami tomay bhalobasi ";
+const ROUNDS: u32 = 200;
+
+fn main() {
+    let parser = Parser::new_phonetic();
+    let convert = |input: &str, output: &mut String| parser.convert_into(input, output);
+
+    println!("{:>8} {:>14} {:>14}", "length", "full", "incremental");
+    for sentences in [1, 4, 16, 64] {
+        let text = SENTENCE.repeat(sentences);
+        let (prefix, key) = text.trim_end().split_at(text.trim_end().len() - 1);
+
+        let mut output = String::new();
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            convert(black_box(prefix), &mut output);
+            convert(black_box(&[prefix, key].concat()), &mut output);
+        }
+        let full = start.elapsed() / (2 * ROUNDS);
+
+        let mut converter = IncrementalConverter::new();
+        converter.update(prefix, convert);
+        let typed = [prefix, key].concat();
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            black_box(converter.update(black_box(&typed), convert));
+            black_box(converter.update(black_box(prefix), convert));
+        }
+        let incremental = start.elapsed() / (2 * ROUNDS);
+
+        println!("{:>8} {:>14?} {:>14?}", text.len(), full, incremental);
+    }
+}
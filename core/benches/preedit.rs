@@ -0,0 +1,129 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Measures the time and heap allocations of a keystroke while typing a long word, through the
+//! preedit and with the transcriber on its own.
+//!
+//! The preedit doesn't allocate once its buffers have grown, whatever the transcriber, so a
+//! keystroke makes no more allocations than the transcriber does by itself. Khipro doesn't
+//! allocate at all. Both transcribers convert the whole word on every key, as no pattern
+//! spans a space but any may span the letters of a word, so the time per key grows with the
+//! word and is the transcriber's.
+//!
+//! Run with `cargo bench -p uo-keyboard-core --bench preedit`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use okkhor::{khipro::KhiproPhonetic, parser::Parser};
+use uo_keyboard_core::preedit::Preedit;
+
+/// Counts allocations made through the global allocator.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// A 40 character word, as far as the keyboard is concerned.
+const WORD: &str = "ontorjatikbhabeprotishthitobishshobiddal";
+const ROUNDS: usize = 200;
+
+/// Measures `f` typing `WORD` a key at a time for a few rounds, returning the time and
+/// allocations per key.
+fn per_key(mut f: impl FnMut(&str)) -> (f64, f64) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for _ in 0..ROUNDS {
+        for end in 1..=WORD.len() {
+            f(black_box(&WORD[..end]));
+        }
+    }
+
+    let keys = (ROUNDS * WORD.len()) as f64;
+    let elapsed = start.elapsed().as_nanos() as f64 / keys;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / keys;
+    (elapsed, allocations)
+}
+
+/// Reports a keystroke through the preedit and with `convert` alone, returning the
+/// allocations per key of both.
+fn report(name: &str, convert: impl Fn(&str, &mut String)) -> (f64, f64) {
+    let mut preedit = Preedit::new();
+    let mut output = String::new();
+    let mut type_word = |input: &str| {
+        if input.len() == 1 {
+            preedit.clear();
+        }
+        black_box(preedit.convert(input, &convert));
+    };
+    let mut convert_alone = |input: &str| {
+        convert(input, &mut output);
+        black_box(&output);
+    };
+
+    // Grows the buffers to fit the word first.
+    per_key(&mut type_word);
+    per_key(&mut convert_alone);
+
+    let (elapsed, allocations) = per_key(type_word);
+    let (alone_elapsed, alone_allocations) = per_key(convert_alone);
+    println!(
+        "{name:<12} {elapsed:>10.0}ns {allocations:>12.2} {alone_elapsed:>10.0}ns {alone_allocations:>12.2}"
+    );
+    (allocations, alone_allocations)
+}
+
+fn main() {
+    assert_eq!(WORD.len(), 40);
+
+    println!(
+        "{:<12} {:>12} {:>12} {:>12} {:>12}",
+        "transcriber", "per key", "allocations", "alone", "allocations"
+    );
+
+    // Stands for an allocation free transcriber, to measure the preedit on its own.
+    let allocations = report("identity", |input, output| {
+        output.clear();
+        output.push_str(input);
+    });
+    assert_eq!(
+        allocations,
+        (0.0, 0.0),
+        "the preedit allocates on a keystroke"
+    );
+
+    let khipro = KhiproPhonetic::new();
+    let allocations = report("khipro", |input, output| khipro.convert_into(input, output));
+    assert_eq!(allocations, (0.0, 0.0), "a Khipro keystroke allocates");
+
+    // The parser allocates a lowercased copy of its input on every conversion.
+    let avro = Parser::new_phonetic();
+    let (allocations, alone) = report("avro", |input, output| avro.convert_into(input, output));
+    assert_eq!(
+        allocations, alone,
+        "the preedit allocates on an Avro keystroke"
+    );
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Trains a next-word prediction model from plain-text Bangla corpora.
+
+use std::process::ExitCode;
+
+use uo_keyboard_core::ngram::{BuildOptions, NgramBuilder};
+
+const USAGE: &str = "\
+Usage: uo-ngram [--min-count N] [--max-followers N] -o MODEL CORPUS...
+
+Trains a next-word prediction model from UTF-8 text files. Copy the model to
+%APPDATA%\\Uo Keyboard\\ngram.bin for the keyboard to use it.";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut options = BuildOptions::default();
+    let mut output = None;
+    let mut corpora = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--min-count" => {
+                options.min_count = value()?.parse().map_err(|e| format!("{arg}: {e}"))?
+            }
+            "--max-followers" => {
+                options.max_followers = value()?.parse().map_err(|e| format!("{arg}: {e}"))?
+            }
+            "-o" | "--output" => output = Some(value()?),
+            "-h" | "--help" => return Err("Help requested".to_owned()),
+            _ => corpora.push(arg),
+        }
+    }
+
+    let output = output.ok_or("no output file given")?;
+    if corpora.is_empty() {
+        return Err("no corpus given".to_owned());
+    }
+
+    let mut builder = NgramBuilder::new();
+    for corpus in &corpora {
+        let text = std::fs::read_to_string(corpus).map_err(|e| format!("{corpus}: {e}"))?;
+        builder.add_text(&text);
+    }
+
+    let model = builder.build(options);
+    std::fs::write(&output, model.to_bytes()).map_err(|e| format!("{output}: {e}"))
+}
@@ -0,0 +1,347 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Dates of the Gregorian calendar and of Bangabda, the Bangla calendar used in Bangladesh,
+//! written out with Bangla names and digits.
+//!
+//! Bangabda years start on 14 April, the first of বৈশাখ, and each month has a fixed length.
+//! Since the revision of 2019, effective from 1426, the first six months have 31 days, ফাল্গুন
+//! has 29, or 30 when it falls in a Gregorian leap year, and the rest have 30. Before it, the
+//! first five months had 31 days, ফাল্গুন 30 or 31, and the rest 30, which is also used for
+//! the years before that calendar was adopted.
+
+use crate::number::bangla_digits;
+
+/// The first year following the revision of 2019.
+pub const REVISION_YEAR: i32 = 1426;
+
+/// Years between Bangabda and the Gregorian calendar, up to 14 April.
+const YEAR_OFFSET: i32 = 593;
+
+/// Month and day of the Bangla new year.
+const NEW_YEAR: (u8, u8) = (4, 14);
+
+const GREGORIAN_MONTHS: [&str; 12] = [
+    "জানুয়ারি",
+    "ফেব্রুয়ারি",
+    "মার্চ",
+    "এপ্রিল",
+    "মে",
+    "জুন",
+    "জুলাই",
+    "আগস্ট",
+    "সেপ্টেম্বর",
+    "অক্টোবর",
+    "নভেম্বর",
+    "ডিসেম্বর",
+];
+
+const BANGABDA_MONTHS: [&str; 12] = [
+    "বৈশাখ",
+    "জ্যৈষ্ঠ",
+    "আষাঢ়",
+    "শ্রাবণ",
+    "ভাদ্র",
+    "আশ্বিন",
+    "কার্তিক",
+    "অগ্রহায়ণ",
+    "পৌষ",
+    "মাঘ",
+    "ফাল্গুন",
+    "চৈত্র",
+];
+
+/// ফাল্গুন, the month taking the leap day.
+const FALGUN: u8 = 11;
+
+const WEEKDAYS: [&str; 7] = [
+    "রবিবার",
+    "সোমবার",
+    "মঙ্গলবার",
+    "বুধবার",
+    "বৃহস্পতিবার",
+    "শুক্রবার",
+    "শনিবার",
+];
+
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// A day of the Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// The date, if the day exists.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        (1..=12)
+            .contains(&month)
+            .then(|| Self::month_len(year, month))
+            .filter(|len| (1..=*len).contains(&day))
+            .map(|_| Date { year, month, day })
+    }
+
+    pub fn month_len(year: i32, month: u8) -> u8 {
+        match month {
+            2 if is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Days since 1 January 1970, negative before it.
+    pub fn to_days(self) -> i64 {
+        // Counts years from March, so that the leap day ends a year.
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` after 1 January 1970, or before it if negative.
+    pub fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Date {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Day of the week, from 0 for Sunday to 6 for Saturday.
+    pub fn weekday(self) -> u8 {
+        // 1 January 1970 was a Thursday.
+        (self.to_days() + 4).rem_euclid(7) as u8
+    }
+
+    pub fn month_name(self) -> &'static str {
+        GREGORIAN_MONTHS[usize::from(self.month - 1)]
+    }
+}
+
+/// A day of the Bangabda calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BangabdaDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl BangabdaDate {
+    /// The date, if the day exists.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        (1..=12)
+            .contains(&month)
+            .then(|| Self::month_len(year, month))
+            .filter(|len| (1..=*len).contains(&day))
+            .map(|_| BangabdaDate { year, month, day })
+    }
+
+    /// Whether ফাল্গুন of `year` falls in a Gregorian leap year, and takes a leap day.
+    pub fn is_leap_year(year: i32) -> bool {
+        is_leap_year(year + YEAR_OFFSET + 1)
+    }
+
+    pub fn month_len(year: i32, month: u8) -> u8 {
+        let long_months = if year >= REVISION_YEAR { 6 } else { 5 };
+        let falgun_len = if year >= REVISION_YEAR { 29 } else { 30 };
+        match month {
+            FALGUN => falgun_len + u8::from(Self::is_leap_year(year)),
+            month if month <= long_months => 31,
+            _ => 30,
+        }
+    }
+
+    /// The Gregorian date of the first day of `year`.
+    fn new_year(year: i32) -> Date {
+        let (month, day) = NEW_YEAR;
+        Date {
+            year: year + YEAR_OFFSET,
+            month,
+            day,
+        }
+    }
+
+    pub fn from_gregorian(date: Date) -> Self {
+        let (new_year_month, new_year_day) = NEW_YEAR;
+        let year = if (date.month, date.day) < (new_year_month, new_year_day) {
+            date.year - YEAR_OFFSET - 1
+        } else {
+            date.year - YEAR_OFFSET
+        };
+
+        let mut days = date.to_days() - Self::new_year(year).to_days();
+        let mut month = 1;
+        loop {
+            let len = i64::from(Self::month_len(year, month));
+            if days < len {
+                break;
+            }
+            days -= len;
+            month += 1;
+        }
+
+        BangabdaDate {
+            year,
+            month,
+            day: days as u8 + 1,
+        }
+    }
+
+    pub fn to_gregorian(self) -> Date {
+        let days_before = (1..self.month)
+            .map(|month| i64::from(Self::month_len(self.year, month)))
+            .sum::<i64>();
+        Date::from_days(Self::new_year(self.year).to_days() + days_before + i64::from(self.day) - 1)
+    }
+
+    pub fn month_name(self) -> &'static str {
+        BANGABDA_MONTHS[usize::from(self.month - 1)]
+    }
+}
+
+/// A time of the day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl Time {
+    /// The part of the day, like সকাল or রাত, as a time is told in Bangla.
+    pub fn part_of_day(self) -> &'static str {
+        match self.hour {
+            4..=5 => "ভোর",
+            6..=11 => "সকাল",
+            12..=14 => "দুপুর",
+            15..=17 => "বিকাল",
+            18..=19 => "সন্ধ্যা",
+            _ => "রাত",
+        }
+    }
+}
+
+/// Writes a day of the month with the suffix it's read with, like ১লা or ৩রা.
+pub fn day_with_suffix(day: u8) -> String {
+    let suffix = match day {
+        1 => "লা",
+        2 | 3 => "রা",
+        4 => "ঠা",
+        5..=18 => "ই",
+        _ => "শে",
+    };
+    format!("{}{suffix}", bangla_digits(&day.to_string()))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Calendar {
+    #[default]
+    Gregorian,
+    Bangabda,
+}
+
+impl Calendar {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gregorian => "gregorian",
+            Self::Bangabda => "bangabda",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Gregorian, Self::Bangabda]
+            .into_iter()
+            .find(|calendar| calendar.name() == name)
+    }
+}
+
+/// A pattern to write a date and time with, in one of the calendars.
+///
+/// The pattern is copied as is, except for these specifiers:
+///
+/// - `%d` the day of the month, and `%o` the day with its suffix, like ৩রা
+/// - `%m` the number of the month, and `%B` its name
+/// - `%Y` the year
+/// - `%A` the name of the weekday
+/// - `%H` the hour from 00 to 23, `%I` the hour from 1 to 12, and `%M` the minute
+/// - `%p` the part of the day, like সকাল
+/// - `%%` a `%`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateFormat {
+    pub calendar: Calendar,
+    pub pattern: String,
+}
+
+impl DateFormat {
+    /// Reads a pattern, optionally preceded by the name of a calendar and a `:`, like
+    /// `bangabda:%d %B %Y`, using the Gregorian calendar by default.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let (calendar, pattern) = text
+            .split_once(':')
+            .and_then(|(name, pattern)| Some((Calendar::from_name(name.trim())?, pattern)))
+            .unwrap_or((Calendar::Gregorian, text));
+        DateFormat {
+            calendar,
+            pattern: pattern.trim().to_owned(),
+        }
+    }
+
+    /// Writes `date` and `time` with the pattern, in Bangla digits.
+    pub fn format(&self, date: Date, time: Time) -> String {
+        let (year, month, day, month_name) = match self.calendar {
+            Calendar::Gregorian => (date.year, date.month, date.day, date.month_name()),
+            Calendar::Bangabda => {
+                let date = BangabdaDate::from_gregorian(date);
+                (date.year, date.month, date.day, date.month_name())
+            }
+        };
+        let digits = |n: &dyn std::fmt::Display| bangla_digits(&n.to_string());
+
+        let mut text = String::with_capacity(self.pattern.len() * 2);
+        let mut chars = self.pattern.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                text.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some('d') => text.push_str(&digits(&day)),
+                Some('o') => text.push_str(&day_with_suffix(day)),
+                Some('m') => text.push_str(&digits(&month)),
+                Some('B') => text.push_str(month_name),
+                Some('Y') => text.push_str(&digits(&year)),
+                Some('A') => text.push_str(WEEKDAYS[usize::from(date.weekday())]),
+                Some('H') => text.push_str(&digits(&format_args!("{:02}", time.hour))),
+                Some('I') => text.push_str(&digits(&((time.hour + 11) % 12 + 1))),
+                Some('M') => text.push_str(&digits(&format_args!("{:02}", time.minute))),
+                Some('p') => text.push_str(time.part_of_day()),
+                Some('%') => text.push('%'),
+                // Anything else isn't a specifier, and is kept as written.
+                Some(ch) => text.extend(['%', ch]),
+                None => text.push('%'),
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! What happens to a composition, and to the key ending it, when it's terminated.
+
+/// An event ending a composition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Terminator {
+    Space,
+    Tab,
+    Enter,
+    /// Arrows, Home, End, Page Up and Page Down.
+    Navigation,
+    Escape,
+    /// The document holding the composition lost the focus.
+    FocusLoss,
+}
+
+impl Terminator {
+    pub const ALL: [Self; 6] = [
+        Self::Space,
+        Self::Tab,
+        Self::Enter,
+        Self::Navigation,
+        Self::Escape,
+        Self::FocusLoss,
+    ];
+
+    /// Whether the terminator is a key, which can be forwarded to the application.
+    pub fn is_key(self) -> bool {
+        self != Self::FocusLoss
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Space => "space",
+            Self::Tab => "tab",
+            Self::Enter => "enter",
+            Self::Navigation => "navigation",
+            Self::Escape => "escape",
+            Self::FocusLoss => "focus_loss",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitAction {
+    /// Commits the composition and leaves the key to the application.
+    CommitAndForward,
+    /// Commits the composition and eats the key.
+    Commit,
+    /// Discards the composition and eats the key.
+    Cancel,
+}
+
+impl CommitAction {
+    pub fn commits(self) -> bool {
+        self != Self::Cancel
+    }
+
+    pub fn forwards_key(self) -> bool {
+        self == Self::CommitAndForward
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CommitAndForward => "commit_and_forward",
+            Self::Commit => "commit",
+            Self::Cancel => "cancel",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::CommitAndForward, Self::Commit, Self::Cancel]
+            .into_iter()
+            .find(|a| a.name() == name)
+    }
+}
+
+const DEFAULT_ACTIONS: [(Terminator, CommitAction); Terminator::ALL.len()] = [
+    (Terminator::Space, CommitAction::CommitAndForward),
+    (Terminator::Tab, CommitAction::CommitAndForward),
+    (Terminator::Enter, CommitAction::Commit),
+    (Terminator::Navigation, CommitAction::CommitAndForward),
+    (Terminator::Escape, CommitAction::Cancel),
+    (Terminator::FocusLoss, CommitAction::Commit),
+];
+
+/// The action taken for each terminator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitPolicy {
+    actions: [CommitAction; Terminator::ALL.len()],
+}
+
+impl Default for CommitPolicy {
+    fn default() -> Self {
+        let mut policy = CommitPolicy {
+            actions: [CommitAction::Commit; Terminator::ALL.len()],
+        };
+        for (terminator, action) in DEFAULT_ACTIONS {
+            policy.set(terminator, action);
+        }
+        policy
+    }
+}
+
+impl CommitPolicy {
+    pub fn action(&self, terminator: Terminator) -> CommitAction {
+        self.actions[terminator as usize]
+    }
+
+    /// Sets the action for `terminator`, where forwarding is dropped for a focus loss as there
+    /// is no key to forward.
+    pub fn set(&mut self, terminator: Terminator, action: CommitAction) {
+        self.actions[terminator as usize] = match action {
+            CommitAction::CommitAndForward if !terminator.is_key() => CommitAction::Commit,
+            action => action,
+        };
+    }
+
+    /// Sets an action from a `terminator = action` setting, like `focus_loss = cancel`.
+    ///
+    /// Returns `false` if either name is unknown.
+    pub fn set_by_name(&mut self, terminator: &str, action: &str) -> bool {
+        match (
+            Terminator::from_name(terminator),
+            CommitAction::from_name(action),
+        ) {
+            (Some(terminator), Some(action)) => {
+                self.set(terminator, action);
+                true
+            }
+            _ => false,
+        }
+    }
+}
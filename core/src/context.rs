@@ -0,0 +1,70 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Text before the caret, standing as the left context of a new composition.
+//!
+//! The transcribers know nothing of text outside their input, so the context is given to them
+//! as roman input: a composition continuing a word after a consonant is converted as if the
+//! consonant was typed first, and its conversion is dropped from the output. Typing `i` after
+//! `ক` then gives the vowel sign `ি` rather than the vowel `ই`.
+
+use crate::text::normalize;
+
+/// Roman strings tried when looking for how a transcriber types each consonant.
+const CANDIDATES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Characters of the text before the caret worth reading.
+pub const CONTEXT_LEN: usize = 4;
+
+/// How a transcriber types each consonant, to stand for the consonants before the caret.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedTable {
+    /// Normalized consonants along with their roman input, longest consonant first.
+    seeds: Vec<(String, String)>,
+}
+
+impl SeedTable {
+    /// Finds the consonants typed by single letters, and letters followed by `h`, with
+    /// `convert` transcribing into the given buffer, replacing its content.
+    pub fn build(mut convert: impl FnMut(&str, &mut String)) -> Self {
+        let letters = CANDIDATES.chars().map(String::from);
+        let digraphs = CANDIDATES.chars().map(|ch| format!("{ch}h"));
+
+        let mut seeds: Vec<(String, String)> = Vec::new();
+        let mut output = String::new();
+        for roman in letters.chain(digraphs) {
+            convert(&roman, &mut output);
+            let consonant = normalize(&output);
+            if is_consonant(&consonant) && !seeds.iter().any(|(c, _)| *c == consonant) {
+                seeds.push((consonant.into_owned(), roman));
+            }
+        }
+
+        seeds.sort_by_key(|(consonant, _)| std::cmp::Reverse(consonant.len()));
+        SeedTable { seeds }
+    }
+
+    /// The roman input standing for the end of `left`, the text before the caret, if it ends
+    /// with a consonant a composition could continue.
+    pub fn seed_for(&self, left: &str) -> Option<&str> {
+        let left = normalize(left);
+        self.seeds
+            .iter()
+            .find(|(consonant, _)| left.ends_with(consonant.as_str()))
+            .map(|(_, roman)| roman.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seeds.is_empty()
+    }
+}
+
+/// Whether `text` is a single consonant, possibly with a nukta.
+fn is_consonant(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|ch| matches!(ch, '\u{0995}'..='\u{09B9}' | '\u{09DC}'..='\u{09DF}'))
+        && matches!(chars.next(), None | Some('\u{09BC}'))
+        && chars.next().is_none()
+}
@@ -0,0 +1,153 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Typing straight into a document, for applications handling compositions poorly.
+//!
+//! Without a composition, the roman input is only known to the IME, and its conversion is
+//! plain text before the caret. Each key converts the input again and replaces the text it
+//! inserted for the previous key, as long as the caret hasn't moved away from it.
+
+use crate::{escape::Escapes, preedit::Preedit};
+
+/// An edit of the text right before the caret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replacement<'a> {
+    /// UTF-16 units to delete before the caret.
+    pub delete: usize,
+    /// Text to insert in their place, leaving the caret after it.
+    pub insert: &'a [u16],
+}
+
+impl Replacement<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.delete == 0 && self.insert.is_empty()
+    }
+}
+
+/// The input typed since the last commit, along with the text inserted for it.
+#[derive(Debug, Clone, Default)]
+pub struct DirectInput {
+    /// Keys as typed, before the transcriber adapts them.
+    typed: String,
+    input: String,
+    preedit: Preedit,
+}
+
+impl DirectInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the delimiters of literal segments, as [`Preedit::set_escapes`] does.
+    pub fn set_escapes(&mut self, escapes: Option<Escapes>) {
+        self.preedit.set_escapes(escapes);
+    }
+
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The text inserted for the input.
+    pub fn text(&self) -> &str {
+        self.preedit.text()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// UTF-16 length of the text before the caret to read, to check it's still the text
+    /// inserted.
+    pub fn context_len(&self) -> usize {
+        self.preedit.utf16().len()
+    }
+
+    /// Whether `left`, the text before the caret, still ends with the text inserted, so that
+    /// it can be replaced.
+    pub fn is_in_place(&self, left: &str) -> bool {
+        left.ends_with(self.preedit.text())
+    }
+
+    /// Appends a key, typed as `typed` and adapted for the transcriber as `adapted`, returning
+    /// the change to the text.
+    pub fn push(
+        &mut self,
+        typed: char,
+        adapted: char,
+        convert: impl FnMut(&str, &mut String),
+    ) -> Replacement<'_> {
+        // The text of an ended input is left to the document.
+        if self.input.is_empty() {
+            self.preedit.clear();
+        }
+        self.typed.push(typed);
+        self.input.push(adapted);
+        self.convert(convert)
+    }
+
+    /// Removes the last key, returning the change to the text, or `None` without any input.
+    pub fn pop(&mut self, convert: impl FnMut(&str, &mut String)) -> Option<Replacement<'_>> {
+        self.typed.pop()?;
+        self.input.pop();
+        Some(self.convert(convert))
+    }
+
+    /// Takes over `text`, right before the caret, as the conversion of an input, e.g. to
+    /// continue typing a word committed before.
+    pub fn resume(&mut self, typed: &str, input: &str, text: &str) {
+        self.clear();
+        self.typed.push_str(typed);
+        self.input.push_str(input);
+        self.preedit.set_text(text);
+    }
+
+    /// Ends the input, leaving the text as it is.
+    pub fn finish(&mut self) {
+        self.clear();
+    }
+
+    /// Ends the input, replacing the text with the keys as typed.
+    pub fn revert(&mut self) -> Replacement<'_> {
+        let typed = std::mem::take(&mut self.typed);
+        self.input.clear();
+        self.set_text(&typed)
+    }
+
+    /// Ends the input, removing the text.
+    pub fn cancel(&mut self) -> Replacement<'_> {
+        self.typed.clear();
+        self.input.clear();
+        self.set_text("")
+    }
+
+    /// Forgets the input, e.g. after the caret has moved away from the text.
+    pub fn clear(&mut self) {
+        self.typed.clear();
+        self.input.clear();
+        self.preedit.clear();
+    }
+
+    fn convert(&mut self, convert: impl FnMut(&str, &mut String)) -> Replacement<'_> {
+        let previous_len = self.preedit.utf16().len();
+        let diff = self.preedit.convert(&self.input, convert);
+        replacement(previous_len, diff.start, self.preedit.utf16())
+    }
+
+    fn set_text(&mut self, text: &str) -> Replacement<'_> {
+        let previous_len = self.preedit.utf16().len();
+        let diff = self.preedit.set_text(text);
+        replacement(previous_len, diff.start, self.preedit.utf16())
+    }
+}
+
+/// Replaces the text from `start`, as the caret is at the end of the previous text.
+fn replacement(previous_len: usize, start: usize, text: &[u16]) -> Replacement<'_> {
+    Replacement {
+        delete: previous_len - start,
+        insert: &text[start..],
+    }
+}
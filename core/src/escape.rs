@@ -0,0 +1,147 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Literal segments of a composition input, kept as typed rather than transcribed.
+//!
+//! Text between an opening and a closing delimiter, like `{iPhone}`, passes through with the
+//! delimiters dropped. Pairs of delimiters nest, so a segment keeps balanced delimiters within
+//! it, like `{f(){}}` keeping `f(){}`, and a segment left open runs to the end of the input.
+//! When both delimiters are the same there is no nesting, and each one toggles a segment.
+
+/// A part of an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// Text to transcribe.
+    Convert(&'a str),
+    /// Text to keep as is, closed by its delimiter.
+    Literal(&'a str),
+    /// Text to keep as is, left open at the end of the input.
+    Unterminated(&'a str),
+}
+
+impl<'a> Segment<'a> {
+    /// The text of the segment, without delimiters.
+    pub fn text(self) -> &'a str {
+        match self {
+            Self::Convert(text) | Self::Literal(text) | Self::Unterminated(text) => text,
+        }
+    }
+}
+
+/// The delimiters around literal segments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escapes {
+    open: String,
+    close: String,
+}
+
+impl Escapes {
+    /// Delimiters `open` and `close`, unless either is empty.
+    pub fn new(open: &str, close: &str) -> Option<Self> {
+        (!open.is_empty() && !close.is_empty()).then(|| Escapes {
+            open: open.to_owned(),
+            close: close.to_owned(),
+        })
+    }
+
+    pub fn open(&self) -> &str {
+        &self.open
+    }
+
+    pub fn close(&self) -> &str {
+        &self.close
+    }
+
+    /// Splits `input` into the segments to transcribe and those to keep.
+    pub fn segments<'a>(&'a self, input: &'a str) -> impl Iterator<Item = Segment<'a>> + 'a {
+        let mut rest = input;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let (segment, len) = self.scan(rest);
+            rest = &rest[len..];
+            Some(segment)
+        })
+    }
+
+    /// Whether `input` ends within a literal segment, where typed keys are kept as is.
+    pub fn is_open(&self, input: &str) -> bool {
+        matches!(self.segments(input).last(), Some(Segment::Unterminated(_)))
+    }
+
+    /// Length of the first word of `input`, up to and including the first space outside of
+    /// literal segments, if there is one.
+    pub fn word_len(&self, input: &str) -> Option<usize> {
+        let mut rest = input;
+        while !rest.is_empty() {
+            let (segment, len) = self.scan(rest);
+            if let Segment::Convert(text) = segment
+                && let Some(space) = text.find(' ')
+            {
+                return Some(input.len() - rest.len() + space + 1);
+            }
+            rest = &rest[len..];
+        }
+        None
+    }
+
+    /// Transcribes the segments of `input` to convert with `convert`, keeping the literal ones
+    /// as they are, into `output`, replacing its content.
+    ///
+    /// `convert` transcribes into the given buffer, replacing its content.
+    pub fn transcribe(
+        &self,
+        input: &str,
+        output: &mut String,
+        mut convert: impl FnMut(&str, &mut String),
+    ) {
+        if !input.contains(self.open.as_str()) {
+            return convert(input, output);
+        }
+
+        output.clear();
+        let mut converted = String::new();
+        for segment in self.segments(input) {
+            match segment {
+                Segment::Convert(text) => {
+                    convert(text, &mut converted);
+                    output.push_str(&converted);
+                }
+                Segment::Literal(text) | Segment::Unterminated(text) => output.push_str(text),
+            }
+        }
+    }
+
+    /// The first segment of `input`, along with its length in `input`, delimiters included.
+    fn scan<'a>(&self, input: &'a str) -> (Segment<'a>, usize) {
+        let Some(start) = input.find(self.open.as_str()) else {
+            return (Segment::Convert(input), input.len());
+        };
+        if start > 0 {
+            return (Segment::Convert(&input[..start]), start);
+        }
+
+        let content_start = self.open.len();
+        let mut depth = 1;
+        let mut pos = content_start;
+        while pos < input.len() {
+            let rest = &input[pos..];
+            if rest.starts_with(self.close.as_str()) {
+                depth -= 1;
+                if depth == 0 {
+                    let segment = Segment::Literal(&input[content_start..pos]);
+                    return (segment, pos + self.close.len());
+                }
+                pos += self.close.len();
+            } else if rest.starts_with(self.open.as_str()) {
+                depth += 1;
+                pos += self.open.len();
+            } else {
+                pos += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+
+        (Segment::Unterminated(&input[content_start..]), input.len())
+    }
+}
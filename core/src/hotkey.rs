@@ -0,0 +1,87 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Keys bound to IME commands in the settings, written like `ctrl+z` or `escape`.
+
+/// A key along with the modifiers held with it.
+///
+/// Keys are identified by Windows virtual key codes, which match the ASCII codes of uppercase
+/// letters and digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+    pub key: u16,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+/// Named keys other than letters, digits and function keys.
+const NAMED_KEYS: &[(&str, u16)] = &[
+    ("backspace", 0x08),
+    ("tab", 0x09),
+    ("enter", 0x0D),
+    ("pause", 0x13),
+    ("escape", 0x1B),
+    ("esc", 0x1B),
+    ("space", 0x20),
+    ("insert", 0x2D),
+    ("delete", 0x2E),
+];
+
+/// Virtual key code of F1, followed by the rest of the function keys up to F24.
+const F1: u16 = 0x70;
+
+impl Hotkey {
+    /// Parses modifiers and a key joined by `+`, like `ctrl+shift+z`, ignoring case and
+    /// spaces around the parts.
+    ///
+    /// Returns `None` for an unknown key or modifier, or anything but one key.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut hotkey = Hotkey {
+            key: 0,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        };
+
+        let mut parts = text.split('+').map(str::trim).map(str::to_ascii_lowercase);
+        let key = parts.next_back()?;
+        for modifier in parts {
+            match modifier.as_str() {
+                "ctrl" | "control" => hotkey.ctrl = true,
+                "alt" => hotkey.alt = true,
+                "shift" => hotkey.shift = true,
+                _ => return None,
+            }
+        }
+
+        hotkey.key = key_code(&key)?;
+        Some(hotkey)
+    }
+
+    /// Whether `key` pressed with the given modifiers is this hotkey.
+    pub fn matches(&self, key: u16, ctrl: bool, alt: bool, shift: bool) -> bool {
+        *self
+            == Hotkey {
+                key,
+                ctrl,
+                alt,
+                shift,
+            }
+    }
+}
+
+fn key_code(name: &str) -> Option<u16> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch @ ('a'..='z' | '0'..='9')), None) => Some(ch.to_ascii_uppercase() as u16),
+        (Some('f'), Some(_)) => match name[1..].parse::<u16>() {
+            Ok(n @ 1..=24) => Some(F1 + n - 1),
+            _ => None,
+        },
+        _ => NAMED_KEYS
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|&(_, code)| code),
+    }
+}
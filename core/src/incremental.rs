@@ -0,0 +1,183 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Incremental transcription of a growing roman input.
+//!
+//! Both the Avro and the Khipro transcribers start afresh after a space: no pattern spans
+//! one, and a space leaves the same context as the start of the input. So the input can be
+//! converted in segments ending at spaces, where only the segments touched by an edit have to
+//! be converted again, keeping the cost of a keystroke flat however long the input grows.
+//! Spaces within literal segments, see [`crate::escape`], don't end a segment.
+
+use crate::escape::Escapes;
+
+/// Keeps the conversion of an input, updating it piecewise as the input changes.
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalConverter {
+    input: String,
+    output: String,
+    /// Input and output byte lengths at the end of each segment ending with a space.
+    segments: Vec<(usize, usize)>,
+    scratch: String,
+    /// Roman input standing for the text before the composition, see [`crate::context`].
+    seed: String,
+    seed_output: String,
+    seeded_input: String,
+    escapes: Option<Escapes>,
+}
+
+impl IncrementalConverter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts `input`, reusing the conversion of segments unchanged since the last update.
+    ///
+    /// `convert` transcribes a segment into the given buffer, replacing its content.
+    pub fn update(&mut self, input: &str, mut convert: impl FnMut(&str, &mut String)) -> &str {
+        let common = common_prefix_len(&self.input, input);
+
+        // A segment stays valid only if it ends, space included, within the common prefix.
+        let stable = self
+            .segments
+            .iter()
+            .rposition(|&(input_end, _)| input_end <= common)
+            .map_or(0, |i| i + 1);
+        self.segments.truncate(stable);
+
+        let (mut input_end, output_end) = self.segments.last().copied().unwrap_or_default();
+        self.output.truncate(output_end);
+
+        let mut rest = &input[input_end..];
+        while !rest.is_empty() {
+            let word_len = match &self.escapes {
+                Some(escapes) => escapes.word_len(rest),
+                None => rest.find(' ').map(|space| space + 1),
+            };
+            let (segment, tail) = rest.split_at(word_len.unwrap_or(rest.len()));
+            rest = tail;
+
+            if input_end == 0 && !self.seed.is_empty() {
+                self.convert_seeded(segment, &mut convert);
+            } else {
+                transcribe(
+                    self.escapes.as_ref(),
+                    segment,
+                    &mut self.scratch,
+                    &mut convert,
+                );
+                self.output.push_str(&self.scratch);
+            }
+
+            input_end += segment.len();
+            if word_len.is_some() {
+                self.segments.push((input_end, self.output.len()));
+            }
+        }
+
+        self.input.clear();
+        self.input.push_str(input);
+        &self.output
+    }
+
+    /// The input of the last update.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The conversion of the last update.
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    /// The words of the last update, each up to and including the space after it, along with
+    /// their conversions.
+    pub fn words(&self) -> impl Iterator<Item = (&str, &str)> {
+        let ends = self.segments.iter().copied();
+        let last = (self.input.len(), self.output.len());
+        let mut start = (0, 0);
+        ends.chain([last]).filter_map(move |end| {
+            let input = self.input.get(start.0..end.0)?;
+            let output = self.output.get(start.1..end.1)?;
+            start = end;
+            (!input.is_empty()).then_some((input, output))
+        })
+    }
+
+    /// Sets the roman input converted before the first segment, whose own conversion is then
+    /// dropped from the output. An empty seed stands for no context.
+    pub fn set_seed(&mut self, seed: &str, mut convert: impl FnMut(&str, &mut String)) {
+        self.seed.clear();
+        self.seed.push_str(seed);
+        self.seed_output.clear();
+        if !seed.is_empty() {
+            convert(seed, &mut self.seed_output);
+        }
+
+        // The first segment has to be converted again.
+        self.input.clear();
+        self.segments.clear();
+    }
+
+    /// Sets the delimiters of literal segments, kept as typed, where `None` transcribes
+    /// everything.
+    pub fn set_escapes(&mut self, escapes: Option<Escapes>) {
+        self.escapes = escapes;
+
+        // Everything has to be converted again.
+        self.input.clear();
+        self.segments.clear();
+    }
+
+    /// Empties the input for a new composition, dropping the seed too.
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.output.clear();
+        self.segments.clear();
+        self.seed.clear();
+        self.seed_output.clear();
+    }
+
+    fn convert_seeded(&mut self, segment: &str, convert: &mut impl FnMut(&str, &mut String)) {
+        self.seeded_input.clear();
+        self.seeded_input.push_str(&self.seed);
+        self.seeded_input.push_str(segment);
+        transcribe(
+            self.escapes.as_ref(),
+            &self.seeded_input,
+            &mut self.scratch,
+            &mut *convert,
+        );
+
+        // The seed may not survive the segment, e.g. merging into a different letter, in
+        // which case the segment goes without context.
+        match self.scratch.strip_prefix(self.seed_output.as_str()) {
+            Some(rest) => self.output.push_str(rest),
+            None => {
+                transcribe(self.escapes.as_ref(), segment, &mut self.scratch, convert);
+                self.output.push_str(&self.scratch);
+            }
+        }
+    }
+}
+
+/// Transcribes `input` with `convert`, keeping its literal segments if there are `escapes`.
+fn transcribe(
+    escapes: Option<&Escapes>,
+    input: &str,
+    output: &mut String,
+    mut convert: impl FnMut(&str, &mut String),
+) {
+    match escapes {
+        Some(escapes) => escapes.transcribe(input, output, convert),
+        None => convert(input, output),
+    }
+}
+
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    let len = a.bytes().zip(b.bytes()).take_while(|(a, b)| a == b).count();
+    (0..=len)
+        .rev()
+        .find(|&i| b.is_char_boundary(i))
+        .unwrap_or(0)
+}
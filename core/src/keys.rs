@@ -0,0 +1,161 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Characters typed under the active keyboard layout, whichever it is.
+//!
+//! The layout maps a key to any number of UTF-16 code units, or to a dead key modifying the
+//! next one. The layout combines a dead key with the next key itself, as it would without the
+//! IME, which only keeps track of a dead key waiting. The transcribers only know ASCII, so
+//! anything else typed among roman input is passed through unchanged.
+
+/// Characters a single key can produce, beyond which the rest are dropped.
+pub const MAX_KEY_CHARS: usize = 4;
+
+/// Characters produced by a key, without allocating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyChars {
+    chars: [char; MAX_KEY_CHARS],
+    len: usize,
+}
+
+impl KeyChars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a character, returning `false` if there is no room left for it.
+    pub fn push(&mut self, ch: char) -> bool {
+        if self.len == MAX_KEY_CHARS {
+            return false;
+        }
+        self.chars[self.len] = ch;
+        self.len += 1;
+        true
+    }
+
+    pub fn as_slice(&self) -> &[char] {
+        &self.chars[..self.len]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl FromIterator<char> for KeyChars {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut chars = Self::new();
+        for ch in iter {
+            if !chars.push(ch) {
+                break;
+            }
+        }
+        chars
+    }
+}
+
+/// What a key produced under the layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyInput {
+    /// Nothing, like a key without a character.
+    None,
+    /// A dead key, waiting to modify the next key.
+    Dead(char),
+    Chars(KeyChars),
+}
+
+impl KeyInput {
+    /// Decodes the result of `ToUnicodeEx`, where `count` is the number of code units written
+    /// to `units`, or negative for a dead key.
+    ///
+    /// Unpaired surrogates are dropped.
+    pub fn decode(units: &[u16], count: i32) -> Self {
+        if count < 0 {
+            return match char::decode_utf16(units.iter().copied().take(1)).next() {
+                Some(Ok(ch)) => Self::Dead(ch),
+                _ => Self::None,
+            };
+        }
+
+        let count = (count as usize).min(units.len());
+        let chars: KeyChars = char::decode_utf16(units[..count].iter().copied())
+            .filter_map(Result::ok)
+            .collect();
+        if chars.is_empty() {
+            Self::None
+        } else {
+            Self::Chars(chars)
+        }
+    }
+}
+
+/// Keeps track of a dead key waiting for the next key, which the layout combines it with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DeadKeys {
+    pending: Option<char>,
+}
+
+impl DeadKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The dead key waiting for the next key.
+    pub fn pending(&self) -> Option<char> {
+        self.pending
+    }
+
+    pub fn clear(&mut self) {
+        self.pending = None;
+    }
+
+    /// Takes the output of a key, returning the characters to type.
+    ///
+    /// A dead key is held until a key gives characters, which already hold whatever the layout
+    /// made of the dead key: the accented letter, the dead key followed by a letter it doesn't
+    /// combine with, or the dead key alone after a space. A key without a character leaves the
+    /// dead key waiting.
+    pub fn feed(&mut self, input: KeyInput) -> KeyInput {
+        match input {
+            KeyInput::None => KeyInput::None,
+            KeyInput::Dead(ch) => {
+                self.pending = Some(ch);
+                KeyInput::None
+            }
+            KeyInput::Chars(_) => {
+                self.pending = None;
+                input
+            }
+        }
+    }
+}
+
+/// Transcribes the ASCII runs of `input` with `convert`, passing anything else through.
+///
+/// `convert` transcribes into the given buffer, replacing its content, as does this.
+pub fn transcribe_ascii(
+    input: &str,
+    output: &mut String,
+    mut convert: impl FnMut(&str, &mut String),
+) {
+    if input.is_ascii() {
+        return convert(input, output);
+    }
+
+    output.clear();
+    let mut converted = String::new();
+    let mut rest = input;
+    while !rest.is_empty() {
+        let ascii_len = rest.find(|ch: char| !ch.is_ascii()).unwrap_or(rest.len());
+        let (ascii, tail) = rest.split_at(ascii_len);
+        if !ascii.is_empty() {
+            convert(ascii, &mut converted);
+            output.push_str(&converted);
+        }
+
+        let other_len = tail.find(|ch: char| ch.is_ascii()).unwrap_or(tail.len());
+        let (other, tail) = tail.split_at(other_len);
+        output.push_str(other);
+        rest = tail;
+    }
+}
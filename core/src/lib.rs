@@ -0,0 +1,24 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Platform independent parts of the Ũõ Keyboard IME.
+
+pub mod calendar;
+pub mod commit;
+pub mod context;
+pub mod direct;
+pub mod escape;
+pub mod hotkey;
+pub mod incremental;
+pub mod keys;
+pub mod ngram;
+pub mod number;
+pub mod ops;
+pub mod phrase;
+pub mod preedit;
+pub mod recent;
+pub mod shortcode;
+pub mod sink;
+pub mod spell;
+pub mod state;
+pub mod text;
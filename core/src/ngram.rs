@@ -0,0 +1,390 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Next-word prediction from bigram and trigram statistics.
+//!
+//! A model is trained from plain text with [`NgramBuilder`] and stored in a compact binary
+//! format, where all numbers are unsigned LEB128 varints:
+//!
+//! ```text
+//! "UONG" version:u8
+//! word_count { byte_len utf8_bytes }
+//! bigram_count { context follower_count { follower } }
+//! trigram_count { context1 context2 follower_count { follower } }
+//! ```
+//!
+//! Words are referred to by their index in the word list, and followers are stored most
+//! frequent first.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::text::{Token, tokenize};
+
+const MAGIC: &[u8; 4] = b"UONG";
+const VERSION: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelError {
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    InvalidUtf8,
+    InvalidWordId(u32),
+}
+
+impl std::fmt::Display for ModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidMagic => f.write_str("not an n-gram model"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported n-gram model version {version}")
+            }
+            Self::Truncated => f.write_str("truncated n-gram model"),
+            Self::InvalidUtf8 => f.write_str("n-gram model has a word with invalid UTF-8"),
+            Self::InvalidWordId(id) => write!(f, "n-gram model refers to unknown word {id}"),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NgramModel {
+    words: Vec<String>,
+    ids: HashMap<String, u32>,
+    bigrams: HashMap<u32, Vec<u32>>,
+    trigrams: HashMap<(u32, u32), Vec<u32>>,
+}
+
+impl NgramModel {
+    /// Predicts up to `limit` words following `history`, the most likely first.
+    ///
+    /// `history` holds the preceding words, oldest first. Trigram predictions are preferred,
+    /// with bigram predictions filling the rest.
+    pub fn predict<'a>(&'a self, history: &[&str], limit: usize) -> Vec<&'a str> {
+        let ids = history
+            .iter()
+            .rev()
+            .take(2)
+            .map(|word| self.ids.get(*word).copied())
+            .collect::<Vec<_>>();
+
+        let trigram_followers = match ids[..] {
+            [Some(last), Some(second_last)] => self.trigrams.get(&(second_last, last)),
+            _ => None,
+        };
+        let bigram_followers = match ids.first() {
+            Some(Some(last)) => self.bigrams.get(last),
+            _ => None,
+        };
+
+        let mut predictions = Vec::with_capacity(limit);
+        for &id in trigram_followers
+            .into_iter()
+            .chain(bigram_followers)
+            .flatten()
+        {
+            if predictions.len() == limit {
+                break;
+            }
+            let word = self.words[id as usize].as_str();
+            if !predictions.contains(&word) {
+                predictions.push(word);
+            }
+        }
+        predictions
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+
+        write_varint(&mut out, self.words.len() as u32);
+        for word in &self.words {
+            write_varint(&mut out, word.len() as u32);
+            out.extend_from_slice(word.as_bytes());
+        }
+
+        // Sorted for a deterministic output.
+        let mut bigrams = self.bigrams.iter().collect::<Vec<_>>();
+        bigrams.sort_unstable_by_key(|(context, _)| **context);
+        write_varint(&mut out, bigrams.len() as u32);
+        for (&context, followers) in bigrams {
+            write_varint(&mut out, context);
+            write_followers(&mut out, followers);
+        }
+
+        let mut trigrams = self.trigrams.iter().collect::<Vec<_>>();
+        trigrams.sort_unstable_by_key(|(context, _)| **context);
+        write_varint(&mut out, trigrams.len() as u32);
+        for (&(first, second), followers) in trigrams {
+            write_varint(&mut out, first);
+            write_varint(&mut out, second);
+            write_followers(&mut out, followers);
+        }
+
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ModelError> {
+        let mut reader = Reader(bytes);
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(ModelError::InvalidMagic);
+        }
+        match reader.take(1)?[0] {
+            VERSION => {}
+            version => return Err(ModelError::UnsupportedVersion(version)),
+        }
+
+        let word_count = reader.varint()?;
+        let mut words = Vec::with_capacity(word_count.min(1 << 16) as usize);
+        for _ in 0..word_count {
+            let len = reader.varint()? as usize;
+            let word =
+                std::str::from_utf8(reader.take(len)?).map_err(|_| ModelError::InvalidUtf8)?;
+            words.push(word.to_owned());
+        }
+
+        let word_id = |reader: &mut Reader<'_>| {
+            let id = reader.varint()?;
+            if (id as usize) < words.len() {
+                Ok(id)
+            } else {
+                Err(ModelError::InvalidWordId(id))
+            }
+        };
+        let followers = |reader: &mut Reader<'_>| {
+            let count = reader.varint()?;
+            (0..count)
+                .map(|_| word_id(reader))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let mut bigrams = HashMap::new();
+        for _ in 0..reader.varint()? {
+            let context = word_id(&mut reader)?;
+            bigrams.insert(context, followers(&mut reader)?);
+        }
+
+        let mut trigrams = HashMap::new();
+        for _ in 0..reader.varint()? {
+            let context = (word_id(&mut reader)?, word_id(&mut reader)?);
+            trigrams.insert(context, followers(&mut reader)?);
+        }
+
+        let ids = words
+            .iter()
+            .enumerate()
+            .map(|(id, word)| (word.clone(), id as u32))
+            .collect();
+
+        Ok(NgramModel {
+            words,
+            ids,
+            bigrams,
+            trigrams,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildOptions {
+    /// N-grams seen fewer times than this are dropped.
+    pub min_count: u32,
+    /// Number of followers kept per context.
+    pub max_followers: usize,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        BuildOptions {
+            min_count: 2,
+            max_followers: 8,
+        }
+    }
+}
+
+/// Counts bigrams and trigrams of a Bangla corpus to build an [`NgramModel`].
+///
+/// N-grams never span anything but whitespace, so punctuation, digits and non-Bangla text
+/// all break the context.
+#[derive(Debug, Clone, Default)]
+pub struct NgramBuilder {
+    words: Vec<String>,
+    ids: HashMap<String, u32>,
+    bigrams: HashMap<(u32, u32), u32>,
+    trigrams: HashMap<(u32, u32, u32), u32>,
+}
+
+impl NgramBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_text(&mut self, text: &str) {
+        let mut history = [None, None];
+
+        for (_, token) in tokenize(text) {
+            let Token::Word(word) = token else {
+                history = [None, None];
+                continue;
+            };
+
+            let id = self.intern(word);
+            if let Some(last) = history[1] {
+                *self.bigrams.entry((last, id)).or_default() += 1;
+                if let Some(second_last) = history[0] {
+                    *self.trigrams.entry((second_last, last, id)).or_default() += 1;
+                }
+            }
+            history = [history[1], Some(id)];
+        }
+    }
+
+    pub fn build(&self, options: BuildOptions) -> NgramModel {
+        let mut bigrams = HashMap::<u32, Vec<(u32, u32)>>::new();
+        for (&(context, follower), &count) in &self.bigrams {
+            if count >= options.min_count {
+                bigrams.entry(context).or_default().push((follower, count));
+            }
+        }
+
+        let mut trigrams = HashMap::<(u32, u32), Vec<(u32, u32)>>::new();
+        for (&(first, second, follower), &count) in &self.trigrams {
+            if count >= options.min_count {
+                trigrams
+                    .entry((first, second))
+                    .or_default()
+                    .push((follower, count));
+            }
+        }
+
+        // Only words still referred to after pruning make it to the model.
+        let mut model = NgramModel::default();
+        let mut remap = |id: u32| {
+            let word = &self.words[id as usize];
+            *model.ids.entry(word.clone()).or_insert_with(|| {
+                model.words.push(word.clone());
+                model.words.len() as u32 - 1
+            })
+        };
+        let rank = |mut followers: Vec<(u32, u32)>, remap: &mut dyn FnMut(u32) -> u32| {
+            followers.sort_unstable_by(|(a, a_count), (b, b_count)| {
+                b_count
+                    .cmp(a_count)
+                    .then_with(|| self.words[*a as usize].cmp(&self.words[*b as usize]))
+            });
+            followers.truncate(options.max_followers);
+            followers
+                .into_iter()
+                .map(|(follower, _)| remap(follower))
+                .collect::<Vec<_>>()
+        };
+
+        let mut ranked_bigrams = HashMap::with_capacity(bigrams.len());
+        for (context, followers) in bigrams {
+            let followers = rank(followers, &mut remap);
+            ranked_bigrams.insert(remap(context), followers);
+        }
+
+        let mut ranked_trigrams = HashMap::with_capacity(trigrams.len());
+        for ((first, second), followers) in trigrams {
+            let followers = rank(followers, &mut remap);
+            ranked_trigrams.insert((remap(first), remap(second)), followers);
+        }
+
+        model.bigrams = ranked_bigrams;
+        model.trigrams = ranked_trigrams;
+        model
+    }
+
+    fn intern(&mut self, word: &str) -> u32 {
+        if let Some(&id) = self.ids.get(word) {
+            return id;
+        }
+        let id = self.words.len() as u32;
+        self.words.push(word.to_owned());
+        self.ids.insert(word.to_owned(), id);
+        id
+    }
+}
+
+/// The last few committed words, the context for predictions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordHistory {
+    words: VecDeque<String>,
+}
+
+impl WordHistory {
+    const CAPACITY: usize = 2;
+
+    /// Records committed text, where anything but whitespace between words resets the context.
+    pub fn push_text(&mut self, text: &str) {
+        for (_, token) in tokenize(text) {
+            match token {
+                Token::Word(word) => {
+                    if self.words.len() == Self::CAPACITY {
+                        self.words.pop_front();
+                    }
+                    self.words.push_back(word.to_owned());
+                }
+                Token::Other(_) => self.words.clear(),
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The recorded words, oldest first.
+    pub fn words(&self) -> Vec<&str> {
+        self.words.iter().map(String::as_str).collect()
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_followers(out: &mut Vec<u8>, followers: &[u32]) {
+    write_varint(out, followers.len() as u32);
+    for &follower in followers {
+        write_varint(out, follower);
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ModelError> {
+        if self.0.len() < len {
+            return Err(ModelError::Truncated);
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<u32, ModelError> {
+        let mut value = 0u32;
+        for shift in (0..32).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u32::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ModelError::Truncated)
+    }
+}
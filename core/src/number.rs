@@ -0,0 +1,380 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Numbers written out in Bangla: in words, in digits grouped by lakh and crore, as ordinals
+//! and as taka amounts.
+//!
+//! Numbers are read from ASCII or Bangla digits, with an optional fraction after a `.`, like
+//! `12345.50`. Beyond a crore, the count of crores is written out again, so ১০০০০০০০০০ is
+//! একশো কোটি, and the digits keep being grouped in pairs.
+
+/// Words for 0 to 99, which don't follow a pattern in Bangla.
+const WORDS: [&str; 100] = [
+    "শূন্য",
+    "এক",
+    "দুই",
+    "তিন",
+    "চার",
+    "পাঁচ",
+    "ছয়",
+    "সাত",
+    "আট",
+    "নয়",
+    "দশ",
+    "এগারো",
+    "বারো",
+    "তেরো",
+    "চৌদ্দ",
+    "পনেরো",
+    "ষোলো",
+    "সতেরো",
+    "আঠারো",
+    "উনিশ",
+    "বিশ",
+    "একুশ",
+    "বাইশ",
+    "তেইশ",
+    "চব্বিশ",
+    "পঁচিশ",
+    "ছাব্বিশ",
+    "সাতাশ",
+    "আটাশ",
+    "ঊনত্রিশ",
+    "ত্রিশ",
+    "একত্রিশ",
+    "বত্রিশ",
+    "তেত্রিশ",
+    "চৌত্রিশ",
+    "পঁয়ত্রিশ",
+    "ছত্রিশ",
+    "সাঁইত্রিশ",
+    "আটত্রিশ",
+    "ঊনচল্লিশ",
+    "চল্লিশ",
+    "একচল্লিশ",
+    "বিয়াল্লিশ",
+    "তেতাল্লিশ",
+    "চুয়াল্লিশ",
+    "পঁয়তাল্লিশ",
+    "ছেচল্লিশ",
+    "সাতচল্লিশ",
+    "আটচল্লিশ",
+    "ঊনপঞ্চাশ",
+    "পঞ্চাশ",
+    "একান্ন",
+    "বাহান্ন",
+    "তিপ্পান্ন",
+    "চুয়ান্ন",
+    "পঞ্চান্ন",
+    "ছাপ্পান্ন",
+    "সাতান্ন",
+    "আটান্ন",
+    "ঊনষাট",
+    "ষাট",
+    "একষট্টি",
+    "বাষট্টি",
+    "তেষট্টি",
+    "চৌষট্টি",
+    "পঁয়ষট্টি",
+    "ছেষট্টি",
+    "সাতষট্টি",
+    "আটষট্টি",
+    "ঊনসত্তর",
+    "সত্তর",
+    "একাত্তর",
+    "বাহাত্তর",
+    "তিয়াত্তর",
+    "চুয়াত্তর",
+    "পঁচাত্তর",
+    "ছিয়াত্তর",
+    "সাতাত্তর",
+    "আটাত্তর",
+    "ঊনআশি",
+    "আশি",
+    "একাশি",
+    "বিরাশি",
+    "তিরাশি",
+    "চুরাশি",
+    "পঁচাশি",
+    "ছিয়াশি",
+    "সাতাশি",
+    "অষ্টাশি",
+    "ঊননব্বই",
+    "নব্বই",
+    "একানব্বই",
+    "বিরানব্বই",
+    "তিরানব্বই",
+    "চুরানব্বই",
+    "পঁচানব্বই",
+    "ছিয়ানব্বই",
+    "সাতানব্বই",
+    "আটানব্বই",
+    "নিরানব্বই",
+];
+
+/// Units above a hundred, largest first, along with their values.
+const UNITS: [(u64, &str); 3] = [(10_000_000, "কোটি"), (100_000, "লাখ"), (1_000, "হাজার")];
+
+const HUNDRED: &str = "শো";
+const POINT: &str = "দশমিক";
+const TAKA: &str = "টাকা";
+const PAISA: &str = "পয়সা";
+const TAKA_SIGN: char = '৳';
+
+/// Ordinal suffixes of one to ten, beyond which `তম` is used.
+const ORDINAL_SUFFIXES: [&str; 10] = ["ম", "য়", "য়", "র্থ", "ম", "ষ্ঠ", "ম", "ম", "ম", "ম"];
+const ORDINAL_SUFFIX: &str = "তম";
+
+/// Writes `n` out in words, like বারো হাজার তিনশো পঁয়তাল্লিশ for 12345.
+pub fn words(n: u64) -> String {
+    let mut words = String::new();
+    push_words(&mut words, n);
+    words
+}
+
+fn push_words(words: &mut String, mut n: u64) {
+    if n < 100 {
+        return push_word(words, WORDS[n as usize]);
+    }
+
+    for (value, unit) in UNITS {
+        if n >= value {
+            push_words(words, n / value);
+            push_word(words, unit);
+            n %= value;
+        }
+    }
+    if n >= 100 {
+        push_word(words, WORDS[(n / 100) as usize]);
+        words.push_str(HUNDRED);
+        n %= 100;
+    }
+    if n > 0 {
+        push_word(words, WORDS[n as usize]);
+    }
+}
+
+fn push_word(words: &mut String, word: &str) {
+    if !words.is_empty() {
+        words.push(' ');
+    }
+    words.push_str(word);
+}
+
+/// Replaces ASCII digits in `text` with Bangla digits.
+pub fn bangla_digits(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch.to_digit(10) {
+            Some(digit) if ch.is_ascii() => char::from_u32('০' as u32 + digit).unwrap_or(ch),
+            _ => ch,
+        })
+        .collect()
+}
+
+/// Writes `n` in Bangla digits grouped by thousand, lakh and crore, like ১২,৩৪,৫৬৭.
+pub fn group(n: u64) -> String {
+    let digits = n.to_string();
+    let head_len = digits.len().saturating_sub(3);
+    let (head, tail) = digits.split_at(head_len);
+
+    let mut grouped = String::with_capacity(digits.len() * 4);
+    for (i, ch) in head.chars().enumerate() {
+        if i > 0 && (head_len - i).is_multiple_of(2) {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    if !head.is_empty() {
+        grouped.push(',');
+    }
+    grouped.push_str(tail);
+
+    bangla_digits(&grouped)
+}
+
+/// Writes `n` as an ordinal in Bangla digits, like ১ম, ২য় or ১১তম.
+pub fn ordinal(n: u64) -> String {
+    let suffix = match n {
+        1..=10 => ORDINAL_SUFFIXES[n as usize - 1],
+        _ => ORDINAL_SUFFIX,
+    };
+    format!("{}{suffix}", bangla_digits(&n.to_string()))
+}
+
+/// A number read from digits, with an optional fraction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub whole: u64,
+    /// ASCII digits after the decimal point, empty for a whole number.
+    pub fraction: String,
+}
+
+impl Number {
+    /// Reads ASCII or Bangla digits, optionally followed by a `.` and more digits.
+    ///
+    /// Returns `None` for anything else, or a whole part too large.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (whole, fraction) = match text.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (text, None),
+        };
+
+        let whole = ascii_digits(whole)?;
+        let fraction = match fraction {
+            Some(fraction) => ascii_digits(fraction)?,
+            None => String::new(),
+        };
+        Some(Number {
+            whole: whole.parse().ok()?,
+            fraction,
+        })
+    }
+
+    pub fn is_whole(&self) -> bool {
+        self.fraction.is_empty()
+    }
+
+    /// The number in words, reading the fraction digit by digit, like বারো দশমিক পাঁচ.
+    pub fn words(&self) -> String {
+        let mut text = words(self.whole);
+        if !self.is_whole() {
+            push_word(&mut text, POINT);
+            for digit in self.fraction.bytes() {
+                push_word(&mut text, WORDS[usize::from(digit - b'0')]);
+            }
+        }
+        text
+    }
+
+    /// The number in grouped Bangla digits, like ১২,৩৪৫.৫.
+    pub fn digits(&self) -> String {
+        let mut text = group(self.whole);
+        if !self.is_whole() {
+            text.push('.');
+            text.push_str(&bangla_digits(&self.fraction));
+        }
+        text
+    }
+
+    /// The number as an ordinal, unless it has a fraction.
+    pub fn ordinal(&self) -> Option<String> {
+        self.is_whole().then(|| ordinal(self.whole))
+    }
+
+    /// Paisa of the number as an amount of taka, unless its fraction is finer than a paisa.
+    pub fn paisa(&self) -> Option<u8> {
+        match self.fraction.as_bytes() {
+            [] => Some(0),
+            [tens] => Some((tens - b'0') * 10),
+            [tens, ones] => Some((tens - b'0') * 10 + (ones - b'0')),
+            _ => None,
+        }
+    }
+
+    /// The number as an amount of taka, like ৳১২,৩৪৫.৫০.
+    pub fn taka(&self) -> Option<String> {
+        let paisa = self.paisa()?;
+        let paisa = bangla_digits(&format!("{paisa:02}"));
+        Some(format!("{TAKA_SIGN}{}.{paisa}", group(self.whole)))
+    }
+
+    /// The number as an amount of taka in words, like বারো হাজার টাকা পঞ্চাশ পয়সা.
+    ///
+    /// Zero paisa are left out, as are zero taka along with some paisa.
+    pub fn taka_words(&self) -> Option<String> {
+        let paisa = self.paisa()?;
+
+        let mut text = String::new();
+        if self.whole > 0 || paisa == 0 {
+            push_words(&mut text, self.whole);
+            push_word(&mut text, TAKA);
+        }
+        if paisa > 0 {
+            push_words(&mut text, paisa.into());
+            push_word(&mut text, PAISA);
+        }
+        Some(text)
+    }
+}
+
+/// `text` in ASCII digits, if it's made of ASCII or Bangla digits only.
+fn ascii_digits(text: &str) -> Option<String> {
+    if text.is_empty() {
+        return None;
+    }
+    text.chars()
+        .map(|ch| match ch {
+            '0'..='9' => Some(ch),
+            '০'..='৯' => char::from_digit(ch as u32 - '০' as u32, 10),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A way of writing a number out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberForm {
+    Words,
+    Digits,
+    Ordinal,
+    Taka,
+    TakaWords,
+}
+
+impl NumberForm {
+    pub const ALL: [Self; 5] = [
+        Self::Words,
+        Self::Digits,
+        Self::Ordinal,
+        Self::Taka,
+        Self::TakaWords,
+    ];
+
+    /// Letters following the digits to pick the form, where no letters pick words.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Words => "",
+            Self::Digits => "d",
+            Self::Ordinal => "o",
+            Self::Taka => "t",
+            Self::TakaWords => "tw",
+        }
+    }
+
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        let suffix = suffix.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|form| form.suffix() == suffix)
+    }
+
+    /// Writes `number` in this form, if it can be.
+    pub fn write(self, number: &Number) -> Option<String> {
+        match self {
+            Self::Words => Some(number.words()),
+            Self::Digits => Some(number.digits()),
+            Self::Ordinal => number.ordinal(),
+            Self::Taka => number.taka(),
+            Self::TakaWords => number.taka_words(),
+        }
+    }
+}
+
+/// Writes out a number followed by the suffix of a form, like `12345` in words or `12345t`
+/// as taka.
+pub fn convert(code: &str) -> Option<String> {
+    let split = code
+        .find(|ch: char| ch.is_ascii_alphabetic())
+        .unwrap_or(code.len());
+    let (number, suffix) = code.split_at(split);
+    NumberForm::from_suffix(suffix)?.write(&Number::parse(number)?)
+}
+
+/// Every form `text` can be written in, if it's a number.
+pub fn forms(text: &str) -> Vec<(NumberForm, String)> {
+    let Some(number) = Number::parse(text) else {
+        return Vec::new();
+    };
+    NumberForm::ALL
+        .into_iter()
+        .filter_map(|form| Some((form, form.write(&number)?)))
+        .collect()
+}
@@ -0,0 +1,139 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Engine operations queued by key events and applied together within one edit session.
+//!
+//! Documents don't always allow locking them right away, so operations wait in an [`OpQueue`]
+//! until an edit session runs, which applies everything queued so far in order. Key events
+//! arriving in the meantime keep queueing behind them. An operation failing is dropped, and
+//! those queued after it are left for another edit session.
+//!
+//! A key ending a composition that's left to the application is held back the same way while
+//! the commit waits, so that the application doesn't get the key before the committed text.
+
+use std::collections::VecDeque;
+
+/// An operation on the document or the composition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOp {
+    /// Inserts the conversion of a key outside of any composition.
+    Insert(char),
+    /// Appends a key to the composition input, starting a composition if needed.
+    Append(char),
+    /// Removes the last key of the composition input, ending the composition once empty.
+    Backspace,
+    /// Commits the composition.
+    Finish,
+    /// Commits the composition input as typed, rather than its conversion.
+    Revert,
+    /// Discards the composition.
+    Cancel,
+    /// Shows the next of the candidates of the composition text in its place.
+    NextCandidate,
+    /// Sends a key ending the composition, by its virtual-key code, on to the application.
+    Forward(u16),
+}
+
+/// Operations waiting for an edit session, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpQueue {
+    ops: VecDeque<EditOp>,
+}
+
+impl OpQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, op: EditOp) {
+        self.ops.push_back(op);
+    }
+
+    /// Queues `op` behind operations still waiting for an edit session, returning whether
+    /// there were any to queue it behind.
+    pub fn push_behind(&mut self, op: EditOp) -> bool {
+        if self.ops.is_empty() {
+            return false;
+        }
+        self.ops.push_back(op);
+        true
+    }
+
+    pub fn extend(&mut self, ops: impl IntoIterator<Item = EditOp>) {
+        self.ops.extend(ops);
+    }
+
+    /// Takes the oldest operation.
+    pub fn pop(&mut self) -> Option<EditOp> {
+        self.ops.pop_front()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.ops.clear();
+    }
+
+    /// Length in characters the composition input will have once the queue is applied, given
+    /// its current length, where zero means there won't be a composition.
+    pub fn projected_input_len(&self, current: usize) -> usize {
+        self.ops.iter().fold(current, |len, op| match op {
+            EditOp::Insert(_) | EditOp::Forward(_) | EditOp::NextCandidate => len,
+            EditOp::Append(_) => len + 1,
+            EditOp::Backspace => len.saturating_sub(1),
+            EditOp::Finish | EditOp::Revert | EditOp::Cancel => 0,
+        })
+    }
+}
+
+/// Applies queued operations in order, taking each one off the queue with `pop` before `apply`
+/// runs, so that the queue isn't borrowed meanwhile.
+///
+/// When an operation fails, the error is returned right away: the failing operation is gone
+/// from the queue, while the ones after it are still queued.
+pub fn apply_queued<E>(
+    mut pop: impl FnMut() -> Option<EditOp>,
+    mut apply: impl FnMut(EditOp) -> Result<(), E>,
+) -> Result<(), E> {
+    while let Some(op) = pop() {
+        apply(op)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// The edit session has to run before the request returns.
+    Sync,
+    /// The edit session may run later, once the document can be locked.
+    Async,
+}
+
+/// How a document answered a request for an edit session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockResponse {
+    /// The edit session already ran.
+    Done,
+    /// The edit session will run later.
+    Scheduled,
+    /// The document can't be locked synchronously right now.
+    SyncRefused,
+}
+
+/// Requests an edit session with `request`, falling back to an asynchronous one when the
+/// document refuses a synchronous lock, so that queued operations are applied late rather
+/// than lost.
+pub fn request_session<E>(
+    mut request: impl FnMut(LockMode) -> Result<LockResponse, E>,
+) -> Result<LockResponse, E> {
+    match request(LockMode::Sync)? {
+        LockResponse::SyncRefused => request(LockMode::Async),
+        response => Ok(response),
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Compositions spanning a whole phrase rather than a single word.
+//!
+//! In phrase mode, a space is typed into the composition instead of ending it, so a phrase can
+//! be reviewed as a whole before committing it. The converter still works a word at a time,
+//! see [`crate::incremental`], so a longer composition costs no more per keystroke. A phrase
+//! ends on a space after punctuation, or on the terminators other than space.
+//!
+//! Words are converted just like in word mode, but the phrase can then be switched between
+//! its [`PhraseCandidates`], where the words around each word decide which of its alternates
+//! come first.
+
+use crate::text::{Token, tokenize};
+
+/// Characters ending a phrase when a space is typed after them.
+pub const PUNCTUATION: &[char] = &['.', ',', ';', '?', '!'];
+
+/// How much text a composition holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompositionMode {
+    /// A space ends the composition, committing each word on its own.
+    #[default]
+    Word,
+    /// A space is typed into the composition, committing a phrase at once.
+    Phrase,
+}
+
+impl CompositionMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Word => "word",
+            Self::Phrase => "phrase",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Word, Self::Phrase]
+            .into_iter()
+            .find(|mode| mode.name() == name)
+    }
+
+    /// Whether a space typed after `input` is kept in the composition.
+    pub fn keeps_space(self, input: &str) -> bool {
+        self == Self::Phrase && !ends_phrase(input)
+    }
+}
+
+/// Whether `input` ends with punctuation, so that the phrase ends with the next space.
+pub fn ends_phrase(input: &str) -> bool {
+    input.ends_with(PUNCTUATION)
+}
+
+/// Alternates of each word tried in the candidates of a phrase.
+pub const WORD_ALTERNATES: usize = 4;
+
+/// Candidates kept for a phrase, the phrase as converted included.
+pub const CANDIDATE_LIMIT: usize = 9;
+
+/// Conversions of a phrase to choose from, each swapping one of its words for an alternate.
+///
+/// The phrase as converted comes first. The rest are ranked by how many of their words are
+/// likely after the words before them, so that the words around a word decide between its
+/// alternates. The words committed before the phrase count as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhraseCandidates {
+    candidates: Vec<String>,
+    selected: usize,
+}
+
+impl PhraseCandidates {
+    /// Makes the candidates of the phrase made of `words`, given as the roman input and the
+    /// conversion of each word like [`crate::preedit::Preedit::words`] gives them.
+    ///
+    /// `history` holds the words committed before the phrase, oldest first. `alternates` gives
+    /// the alternates of a converted word along with its roman input, and `follows` whether a
+    /// word is likely after the given words, oldest first.
+    pub fn new<'a>(
+        words: impl IntoIterator<Item = (&'a str, &'a str)>,
+        history: &[&str],
+        mut alternates: impl FnMut(&str, &str) -> Vec<String>,
+        follows: impl Fn(&[&str], &str) -> bool,
+    ) -> Self {
+        let mut phrase = String::new();
+        let mut swaps = Vec::new();
+        for (input, output) in words {
+            if let Some((offset, Token::Word(word))) = tokenize(output).next() {
+                let start = phrase.len() + offset;
+                for alternate in alternates(word, input.trim())
+                    .into_iter()
+                    .take(WORD_ALTERNATES)
+                {
+                    swaps.push((start..start + word.len(), alternate));
+                }
+            }
+            phrase.push_str(output);
+        }
+
+        let mut variants = swaps
+            .into_iter()
+            .map(|(range, alternate)| {
+                let mut variant = phrase.clone();
+                variant.replace_range(range, &alternate);
+                (context_score(history, &variant, &follows), variant)
+            })
+            .collect::<Vec<_>>();
+        // Sorting is stable, keeping the earlier words and closer alternates first on a tie.
+        variants.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        let mut candidates = vec![phrase];
+        for (_, variant) in variants {
+            if candidates.len() == CANDIDATE_LIMIT {
+                break;
+            }
+            if !candidates.contains(&variant) {
+                candidates.push(variant);
+            }
+        }
+
+        PhraseCandidates {
+            candidates,
+            selected: 0,
+        }
+    }
+
+    /// The candidates, the phrase as converted first.
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    pub fn selected(&self) -> &str {
+        &self.candidates[self.selected]
+    }
+
+    /// Selects the next candidate, going back to the phrase as converted after the last one.
+    pub fn select_next(&mut self) -> &str {
+        self.selected = (self.selected + 1) % self.candidates.len();
+        self.selected()
+    }
+}
+
+/// Counts the words of `text` likely after the words before them, starting with `history`.
+///
+/// Anything but whitespace between words breaks the context, as it does for predictions.
+fn context_score(history: &[&str], text: &str, follows: impl Fn(&[&str], &str) -> bool) -> usize {
+    let mut context = history.to_vec();
+    let mut score = 0;
+    for (_, token) in tokenize(text) {
+        match token {
+            Token::Word(word) => {
+                score += usize::from(follows(&context, word));
+                context.push(word);
+            }
+            Token::Other(_) => context.clear(),
+        }
+    }
+    score
+}
@@ -0,0 +1,86 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! The text of a composition, in buffers reused from one keystroke to the next.
+
+use crate::{
+    escape::Escapes,
+    incremental::IncrementalConverter,
+    text::{Utf16Diff, utf16_diff},
+};
+
+/// Holds the composition text along with its UTF-16 encoding and that of the previous update.
+///
+/// Once the buffers have grown to fit the longest composition, updates don't allocate.
+#[derive(Debug, Clone, Default)]
+pub struct Preedit {
+    converter: IncrementalConverter,
+    text: String,
+    utf16: Vec<u16>,
+    previous_utf16: Vec<u16>,
+}
+
+impl Preedit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transcribes `input` with `convert` as [`IncrementalConverter::update`] does, returning
+    /// the change to the text.
+    pub fn convert(&mut self, input: &str, convert: impl FnMut(&str, &mut String)) -> Utf16Diff {
+        let output = self.converter.update(input, convert);
+        self.text.clear();
+        self.text.push_str(output);
+        self.encode()
+    }
+
+    /// Sets the roman input standing for the text before the composition, as
+    /// [`IncrementalConverter::set_seed`] does.
+    pub fn set_seed(&mut self, seed: &str, convert: impl FnMut(&str, &mut String)) {
+        self.converter.set_seed(seed, convert);
+    }
+
+    /// Sets the delimiters of literal segments, as [`IncrementalConverter::set_escapes`] does.
+    pub fn set_escapes(&mut self, escapes: Option<Escapes>) {
+        self.converter.set_escapes(escapes);
+    }
+
+    /// Sets the text as is, e.g. to a shortcode symbol, returning the change to the text.
+    pub fn set_text(&mut self, text: &str) -> Utf16Diff {
+        self.text.clear();
+        self.text.push_str(text);
+        self.encode()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The words of the last conversion along with their conversions, as
+    /// [`IncrementalConverter::words`] gives them.
+    ///
+    /// Text set as is isn't split into words.
+    pub fn words(&self) -> impl Iterator<Item = (&str, &str)> {
+        let is_converted = self.converter.output() == self.text;
+        self.converter.words().filter(move |_| is_converted)
+    }
+
+    pub fn utf16(&self) -> &[u16] {
+        &self.utf16
+    }
+
+    /// Empties the text for a new composition, keeping the buffers.
+    pub fn clear(&mut self) {
+        self.converter.clear();
+        self.text.clear();
+        self.utf16.clear();
+        self.previous_utf16.clear();
+    }
+
+    fn encode(&mut self) -> Utf16Diff {
+        std::mem::swap(&mut self.utf16, &mut self.previous_utf16);
+        self.utf16.clear();
+        self.utf16.extend(self.text.encode_utf16());
+        utf16_diff(&self.previous_utf16, &self.utf16)
+    }
+}
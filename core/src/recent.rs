@@ -0,0 +1,119 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Words committed last, to be composed again from their roman input on a backspace, or
+//! turned back into it.
+
+use std::collections::VecDeque;
+
+/// Commits remembered, beyond which the oldest are forgotten.
+pub const CAPACITY: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The composition input the text was converted from.
+    pub roman: String,
+    pub text: String,
+}
+
+/// The last few commits, most recent last.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecentCommits {
+    commits: VecDeque<Commit>,
+}
+
+impl RecentCommits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remembers a commit, unless there was nothing to commit.
+    pub fn push(&mut self, roman: &str, text: &str) {
+        if roman.is_empty() || text.is_empty() {
+            return;
+        }
+        if self.commits.len() == CAPACITY {
+            self.commits.pop_front();
+        }
+        self.commits.push_back(Commit {
+            roman: roman.to_owned(),
+            text: text.to_owned(),
+        });
+    }
+
+    pub fn last(&self) -> Option<&Commit> {
+        self.commits.back()
+    }
+
+    /// Forgets the last commit, returning it.
+    pub fn pop(&mut self) -> Option<Commit> {
+        self.commits.pop_back()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commits.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.commits.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.commits.clear();
+    }
+
+    /// Forgets the commits unless the last one, along with a space or a tab typed after it,
+    /// still ends `left`, the text before the caret once something other than the IME moved it.
+    ///
+    /// A terminator forwarded to the application after a commit is typed by the application
+    /// itself, which moves the caret past the commit without taking it away.
+    pub fn caret_moved(&mut self, left: &str) {
+        if self.find(left).is_none() {
+            self.commits.clear();
+        }
+    }
+
+    /// UTF-16 length of the text to read before the caret to find the last commit there,
+    /// along with a whitespace character typed after it.
+    pub fn context_len(&self) -> Option<usize> {
+        self.last()
+            .map(|commit| commit.text.encode_utf16().count() + 1)
+    }
+
+    /// UTF-16 length at the end of `left`, the text before the caret, taken up by the last
+    /// commit and a space or a tab typed after it, if the commit is still there unchanged.
+    pub fn reopenable_len(&self, left: &str) -> Option<usize> {
+        let (commit, trailer) = self.find(left)?;
+        Some(commit.text.encode_utf16().count() + trailer.encode_utf16().count())
+    }
+
+    /// Forgets the last commit if it's still at the end of `left`, the text before the caret,
+    /// returning its roman input to replace it with, along with what's typed after it.
+    pub fn revert(&mut self, left: &str) -> Option<Reverted> {
+        let (commit, trailer) = self.find(left)?;
+        let reverted = Reverted {
+            len: commit.text.encode_utf16().count() + trailer.encode_utf16().count(),
+            text: format!("{}{trailer}", commit.roman),
+        };
+        self.commits.pop_back();
+        Some(reverted)
+    }
+
+    /// The last commit along with the space or tab after it, if they end `left`.
+    fn find<'a>(&self, left: &'a str) -> Option<(&Commit, &'a str)> {
+        let commit = self.last()?;
+        let trimmed = left.strip_suffix([' ', '\t']).unwrap_or(left);
+        trimmed
+            .ends_with(commit.text.as_str())
+            .then(|| (commit, &left[trimmed.len()..]))
+    }
+}
+
+/// A commit turned back into its roman input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reverted {
+    /// UTF-16 length of the text before the caret to replace.
+    pub len: usize,
+    /// The roman input, followed by the space or tab typed after the commit.
+    pub text: String,
+}
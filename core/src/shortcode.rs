@@ -0,0 +1,217 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Shortcodes like `:smile:` or `:taka:` typed in a composition in place of emoji and symbols.
+//!
+//! A code follows a configurable prefix, `:` in the IME, and may be closed by the prefix again.
+//! Codes missing from the table are tried as the date, or as numbers to write out, once they
+//! are closed.
+
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::{
+    calendar::{Date, DateFormat, Time},
+    number,
+};
+
+const DEFAULT_SHORTCODES: &[(&str, &str)] = &[
+    // Bangla signs
+    ("taka", "৳"),
+    ("rupee", "৲"),
+    ("isshar", "৺"),
+    ("anji", "ঀ"),
+    ("ganda", "৻"),
+    ("danda", "।"),
+    ("dari", "।"),
+    ("ddanda", "॥"),
+    ("ddari", "॥"),
+    ("abbr", "৽"),
+    ("abbreviation", "৽"),
+    ("avagraha", "ঽ"),
+    ("khandata", "ৎ"),
+    ("anusvara", "ৼ"),
+    ("sandhi", "৾"),
+    ("candrabindu", "ঁ"),
+    ("chandrabindu", "ঁ"),
+    ("hasanta", "্"),
+    ("zwj", "\u{200d}"),
+    ("zwnj", "\u{200c}"),
+    // Currency numerators
+    ("ana1", "৴"),
+    ("ana2", "৵"),
+    ("ana3", "৶"),
+    ("ana4", "৷"),
+    ("ana16", "৸"),
+    ("anadenominator", "৹"),
+    // Emoji
+    ("smile", "😄"),
+    ("grin", "😁"),
+    ("laugh", "😂"),
+    ("joy", "😂"),
+    ("rofl", "🤣"),
+    ("wink", "😉"),
+    ("blush", "😊"),
+    ("heart_eyes", "😍"),
+    ("kiss", "😘"),
+    ("thinking", "🤔"),
+    ("neutral", "😐"),
+    ("sad", "😞"),
+    ("cry", "😢"),
+    ("sob", "😭"),
+    ("angry", "😠"),
+    ("rage", "😡"),
+    ("scream", "😱"),
+    ("sleepy", "😴"),
+    ("sunglasses", "😎"),
+    ("pray", "🙏"),
+    ("clap", "👏"),
+    ("ok", "👌"),
+    ("wave", "👋"),
+    ("thumbsup", "👍"),
+    ("+1", "👍"),
+    ("thumbsdown", "👎"),
+    ("-1", "👎"),
+    ("muscle", "💪"),
+    ("heart", "❤️"),
+    ("broken_heart", "💔"),
+    ("fire", "🔥"),
+    ("star", "⭐"),
+    ("sparkles", "✨"),
+    ("tada", "🎉"),
+    ("rose", "🌹"),
+    ("moon", "🌙"),
+    ("sun", "☀️"),
+    ("check", "✅"),
+    ("x", "❌"),
+    ("flag_bd", "🇧🇩"),
+];
+
+/// Code typing the current date and time, unless the table has it, followed by the 1-based
+/// number of the format to use, if not the first.
+const DATE_CODE: &str = "date";
+
+/// Outcome of looking up a composition input as a shortcode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shortcode<'a> {
+    /// The input doesn't start with the prefix and should be transcribed as usual.
+    None,
+    /// The input names a known shortcode, the date, or a number to write out.
+    Symbol(Cow<'a, str>),
+    /// The input starts with the prefix but doesn't name a shortcode (yet).
+    Partial,
+}
+
+/// Table of `:code:` shortcodes to emoji and symbols.
+#[derive(Debug, Clone)]
+pub struct ShortcodeTable {
+    codes: HashMap<String, String>,
+    date_formats: Vec<DateFormat>,
+}
+
+impl Default for ShortcodeTable {
+    fn default() -> Self {
+        ShortcodeTable {
+            codes: DEFAULT_SHORTCODES
+                .iter()
+                .map(|&(code, symbol)| (code.to_owned(), symbol.to_owned()))
+                .collect(),
+            date_formats: Vec::new(),
+        }
+    }
+}
+
+impl ShortcodeTable {
+    /// Adds or overrides shortcodes from `code = symbol` lines.
+    ///
+    /// Blank lines and lines starting with `;` or `#` are ignored.
+    /// Returns the 1-based numbers of malformed lines.
+    pub fn extend_from_str(&mut self, text: &str) -> Vec<usize> {
+        let mut malformed = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with([';', '#']) {
+                continue;
+            }
+
+            match line.split_once('=') {
+                Some((code, symbol)) if !code.trim().is_empty() && !symbol.trim().is_empty() => {
+                    self.insert(code.trim(), symbol.trim());
+                }
+                _ => malformed.push(number + 1),
+            }
+        }
+
+        malformed
+    }
+
+    /// Sets the formats of the date shortcode, picked by their number as in `:date2:`, or the
+    /// first by `:date:`.
+    pub fn set_date_formats(&mut self, formats: Vec<DateFormat>) {
+        self.date_formats = formats;
+    }
+
+    pub fn insert(&mut self, code: &str, symbol: &str) {
+        self.codes.insert(code.to_lowercase(), symbol.to_owned());
+    }
+
+    pub fn get(&self, code: &str) -> Option<&str> {
+        self.codes.get(&code.to_lowercase()).map(String::as_str)
+    }
+
+    /// Interprets a composition input as `<prefix>code` or `<prefix>code<prefix>`.
+    ///
+    /// Codes in the table resolve as soon as they are typed. Other codes are only tried once
+    /// closed, as the date or as numbers followed by the suffix of a form to write them in,
+    /// like `12345` in words or `12345t` as taka, so that a prefix typed before a number isn't
+    /// taken for a shortcode. `now` is only called for the date shortcode.
+    /// An empty prefix disables shortcodes.
+    pub fn resolve(
+        &self,
+        prefix: &str,
+        input: &str,
+        now: impl FnOnce() -> (Date, Time),
+    ) -> Shortcode<'_> {
+        let Some((code, closed)) = code(prefix, input) else {
+            return Shortcode::None;
+        };
+
+        if let Some(symbol) = self.get(code) {
+            return Shortcode::Symbol(symbol.into());
+        }
+        if !closed {
+            return Shortcode::Partial;
+        }
+        let text = match self.date_format(code) {
+            Some(format) => {
+                let (date, time) = now();
+                Some(format.format(date, time))
+            }
+            None => number::convert(code),
+        };
+        text.map_or(Shortcode::Partial, |text| Shortcode::Symbol(text.into()))
+    }
+
+    /// The format picked by a date code, `date` for the first and `dateN` for the Nth.
+    fn date_format(&self, code: &str) -> Option<&DateFormat> {
+        let number = code
+            .get(..DATE_CODE.len())
+            .filter(|name| name.eq_ignore_ascii_case(DATE_CODE))
+            .map(|_| &code[DATE_CODE.len()..])?;
+        let index = match number {
+            "" => 0,
+            number => number.parse::<usize>().ok()?.checked_sub(1)?,
+        };
+        self.date_formats.get(index)
+    }
+}
+
+/// The code of a composition input written as `<prefix>code` or `<prefix>code<prefix>`, and
+/// whether it's closed by the prefix.
+fn code<'a>(prefix: &str, input: &'a str) -> Option<(&'a str, bool)> {
+    let code = input.strip_prefix(prefix).filter(|_| !prefix.is_empty())?;
+    Some(match code.strip_suffix(prefix) {
+        Some(code) => (code, true),
+        None => (code, false),
+    })
+}
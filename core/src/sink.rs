@@ -0,0 +1,92 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Guards unadvising event sinks, so that every advise is paired with an unadvise.
+//!
+//! A sink is unadvised when its guard is dropped, which covers an activation failing midway
+//! as well as a deactivation, while [`SinkGuard::release`] and [`SinkStack::release`] unadvise
+//! explicitly to report failures.
+
+/// A sink advised to a source, along with whatever is needed to unadvise it.
+pub trait Advised {
+    type Error;
+
+    fn unadvise(&self) -> Result<(), Self::Error>;
+}
+
+/// Unadvises its sink when dropped.
+#[derive(Debug)]
+pub struct SinkGuard<T: Advised> {
+    sink: Option<T>,
+}
+
+impl<T: Advised> SinkGuard<T> {
+    /// Guards a sink that was just advised.
+    pub fn new(sink: T) -> Self {
+        SinkGuard { sink: Some(sink) }
+    }
+
+    /// Unadvises the sink, returning the failure to do so.
+    pub fn release(mut self) -> Result<(), T::Error> {
+        self.sink.take().map_or(Ok(()), |sink| sink.unadvise())
+    }
+}
+
+impl<T: Advised> Drop for SinkGuard<T> {
+    fn drop(&mut self) {
+        if let Some(sink) = self.sink.take() {
+            let _ = sink.unadvise();
+        }
+    }
+}
+
+/// Sinks unadvised in the reverse order of being advised.
+#[derive(Debug)]
+pub struct SinkStack<T: Advised> {
+    guards: Vec<SinkGuard<T>>,
+}
+
+impl<T: Advised> Default for SinkStack<T> {
+    fn default() -> Self {
+        SinkStack { guards: Vec::new() }
+    }
+}
+
+impl<T: Advised> SinkStack<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Guards a sink that was just advised.
+    pub fn push(&mut self, sink: T) {
+        self.guards.push(SinkGuard::new(sink));
+    }
+
+    pub fn len(&self) -> usize {
+        self.guards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.guards.is_empty()
+    }
+
+    /// Unadvises every sink, last advised first, returning the first failure.
+    ///
+    /// A failure doesn't stop the remaining sinks from being unadvised.
+    pub fn release(mut self) -> Result<(), T::Error> {
+        let mut result = Ok(());
+        while let Some(guard) = self.guards.pop() {
+            let released = guard.release();
+            if result.is_ok() {
+                result = released;
+            }
+        }
+        result
+    }
+}
+
+impl<T: Advised> Drop for SinkStack<T> {
+    fn drop(&mut self) {
+        while self.guards.pop().is_some() {}
+    }
+}
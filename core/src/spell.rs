@@ -0,0 +1,319 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Bangla spell checking against a word list.
+//!
+//! Suggestions are ranked by the edit distance between graphemes, so a wrong conjunct or
+//! vowel sign counts as a single edit, and by the distance between coarse phonetic keys of
+//! the roman input and the candidate, which favors words sounding like what was typed.
+
+use std::collections::HashSet;
+
+use crate::text::{Token, graphemes, normalize, tokenize};
+
+const BUNDLED_WORDS: &str = include_str!("../data/words.txt");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub word: String,
+    /// Combined distance of the suggestion, lower is closer.
+    pub distance: usize,
+}
+
+/// A word of the checked text missing from the word list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Misspelling<'a> {
+    pub word: &'a str,
+    /// Byte offset of the word in the checked text.
+    pub start: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    word: String,
+    grapheme_count: usize,
+    key: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpellChecker {
+    words: HashSet<String>,
+    entries: Vec<Entry>,
+}
+
+impl SpellChecker {
+    /// A checker knowing only the word list bundled with the keyboard.
+    pub fn bundled() -> Self {
+        let mut checker = Self::default();
+        checker.extend_from_str(BUNDLED_WORDS);
+        checker
+    }
+
+    /// Adds one word per line, ignoring blank lines and lines starting with `#`.
+    pub fn extend_from_str(&mut self, text: &str) {
+        for line in text.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                self.add_word(line);
+            }
+        }
+    }
+
+    /// Adds the words of a Hunspell dictionary, given the text of its `.dic` file.
+    ///
+    /// The word count on the first line, the affix flags after a `/` and the morphological
+    /// fields after a tab are dropped. Affix rules aren't applied, so only the forms listed are
+    /// known.
+    pub fn extend_from_dic(&mut self, text: &str) {
+        let mut lines = text.trim_start_matches('\u{FEFF}').lines().map(str::trim);
+        if lines
+            .clone()
+            .next()
+            .is_some_and(|line| line.parse::<usize>().is_ok())
+        {
+            lines.next();
+        }
+
+        for line in lines {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            let word = entry.split('/').next().unwrap_or_default();
+            if !word.is_empty() {
+                self.add_word(word);
+            }
+        }
+    }
+
+    pub fn add_word(&mut self, word: &str) {
+        let word = normalize(word).into_owned();
+        if self.words.contains(&word) {
+            return;
+        }
+
+        self.entries.push(Entry {
+            grapheme_count: graphemes(&word).count(),
+            key: bangla_key(&word),
+            word: word.clone(),
+        });
+        self.words.insert(word);
+    }
+
+    pub fn is_correct(&self, word: &str) -> bool {
+        self.words.contains(normalize(word).as_ref())
+    }
+
+    /// Finds the Bangla words of `text` missing from the word list.
+    pub fn check_text<'a>(&self, text: &'a str) -> Vec<Misspelling<'a>> {
+        tokenize(text)
+            .filter_map(|(start, token)| match token {
+                Token::Word(word) if !self.is_correct(word) => Some(Misspelling { word, start }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Suggests up to `limit` corrections for `word`, the closest first.
+    ///
+    /// When known, `roman` is the input `word` was typed as, and steers the suggestions
+    /// towards words sounding alike.
+    pub fn suggest(&self, word: &str, roman: Option<&str>, limit: usize) -> Vec<Suggestion> {
+        let word = normalize(word);
+        let word_graphemes = graphemes(&word).collect::<Vec<_>>();
+        let max_distance = if word_graphemes.len() <= 3 { 1 } else { 2 };
+
+        let word_key = bangla_key(&word);
+        let roman_key = roman.map(roman_key);
+
+        let mut suggestions = self
+            .entries
+            .iter()
+            .filter(|entry| entry.word != *word)
+            .filter_map(|entry| {
+                let key_distance = edit_distance(word_key.as_bytes(), entry.key.as_bytes());
+                let roman_distance = roman_key
+                    .as_ref()
+                    .map(|key| edit_distance(key.as_bytes(), entry.key.as_bytes()));
+                let sounds_alike = key_distance == 0 || roman_distance == Some(0);
+
+                if !sounds_alike
+                    && entry.grapheme_count.abs_diff(word_graphemes.len()) > max_distance
+                {
+                    return None;
+                }
+
+                let entry_graphemes = graphemes(&entry.word).collect::<Vec<_>>();
+                let grapheme_distance = edit_distance(&word_graphemes, &entry_graphemes);
+                if !sounds_alike && grapheme_distance > max_distance {
+                    return None;
+                }
+
+                let phonetic_distance =
+                    roman_distance.map_or(key_distance, |d| d.min(key_distance));
+                Some(Suggestion {
+                    word: entry.word.clone(),
+                    distance: 2 * grapheme_distance + phonetic_distance,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        suggestions.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// Offers up to `limit` words sounding just like `word`, as alternates to replace it
+    /// with, the closest in spelling first.
+    ///
+    /// When known, `roman` is the input `word` was typed as, and words read as it are offered
+    /// too.
+    pub fn alternates(&self, word: &str, roman: Option<&str>, limit: usize) -> Vec<Suggestion> {
+        let word = normalize(word);
+        let word_graphemes = graphemes(&word).collect::<Vec<_>>();
+        let keys = [Some(bangla_key(&word)), roman.map(roman_key)];
+
+        let mut alternates = self
+            .entries
+            .iter()
+            .filter(|entry| entry.word != *word)
+            .filter(|entry| keys.iter().flatten().any(|key| *key == entry.key))
+            .map(|entry| {
+                let entry_graphemes = graphemes(&entry.word).collect::<Vec<_>>();
+                Suggestion {
+                    word: entry.word.clone(),
+                    distance: edit_distance(&word_graphemes, &entry_graphemes),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        alternates.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        alternates.truncate(limit);
+        alternates
+    }
+}
+
+/// Optimal string alignment distance, counting adjacent transpositions as one edit.
+fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut rows = [
+        vec![0; b.len() + 1],
+        (0..=b.len()).collect::<Vec<_>>(),
+        vec![0; b.len() + 1],
+    ];
+
+    for i in 1..=a.len() {
+        let [before_previous, previous, current] = &mut rows;
+        current[0] = i;
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+        }
+
+        rows.rotate_left(1);
+    }
+
+    rows[1][b.len()]
+}
+
+/// Reduces Bangla text to a coarse roman key, where similar sounding letters coincide and
+/// `অ` and `ও` are dropped like the inherent vowel.
+fn bangla_key(word: &str) -> String {
+    let mut key = String::with_capacity(word.len() / 2);
+
+    for ch in word.chars() {
+        let sound = match ch {
+            'ক' | 'খ' => "k",
+            'গ' | 'ঘ' => "g",
+            'ঙ' | 'ঞ' | 'ণ' | 'ন' | 'ং' => "n",
+            'চ' | 'ছ' => "c",
+            'জ' | 'ঝ' | 'য' => "j",
+            'ট' | 'ঠ' | 'ত' | 'থ' | 'ৎ' => "t",
+            'ড' | 'ঢ' | 'দ' | 'ধ' => "d",
+            'প' => "p",
+            'ফ' => "f",
+            'ব' | 'ভ' => "b",
+            'ম' => "m",
+            'র' | '\u{09DC}' | '\u{09DD}' => "r",
+            'ল' => "l",
+            'শ' | 'ষ' | 'স' => "s",
+            'হ' | 'ঃ' => "h",
+            '\u{09DF}' => "y",
+            'আ' | 'া' => "a",
+            'ই' | 'ঈ' | 'ি' | 'ী' => "i",
+            'উ' | 'ঊ' | 'ু' | 'ূ' => "u",
+            'ঋ' | 'ৃ' => "ri",
+            'এ' | 'ে' => "e",
+            'ঐ' | 'ৈ' => "oi",
+            'ঔ' | 'ৌ' => "ou",
+            // A nukta turns the preceding letter into a flap or `য়`.
+            '\u{09BC}' => match key.pop() {
+                Some('j') => "y",
+                Some(_) => "r",
+                None => "",
+            },
+            _ => "",
+        };
+        key.push_str(sound);
+    }
+
+    key
+}
+
+/// Reduces roman input to the same kind of key as [`bangla_key`].
+fn roman_key(roman: &str) -> String {
+    const DIGRAPHS: &[(&str, &str)] = &[
+        ("kh", "k"),
+        ("gh", "g"),
+        ("ng", "n"),
+        ("ch", "c"),
+        ("jh", "j"),
+        ("th", "t"),
+        ("dh", "d"),
+        ("ph", "f"),
+        ("bh", "b"),
+        ("sh", "s"),
+        ("rh", "r"),
+        ("ee", "i"),
+        ("oo", "u"),
+        ("ii", "i"),
+        ("uu", "u"),
+        ("aa", "a"),
+    ];
+
+    let roman = roman.to_ascii_lowercase();
+    let mut rest = roman.as_str();
+    let mut key = String::with_capacity(roman.len());
+
+    while let Some(ch) = rest.chars().next() {
+        if let Some((digraph, sound)) = DIGRAPHS.iter().find(|(d, _)| rest.starts_with(d)) {
+            key.push_str(sound);
+            rest = &rest[digraph.len()..];
+            continue;
+        }
+
+        match ch {
+            'z' => key.push('j'),
+            'v' => key.push('b'),
+            'q' => key.push('k'),
+            'x' => key.push_str("ks"),
+            // Neither the inherent vowel nor `ও` and `ব`-phola make it to the key.
+            'o' | 'w' => {}
+            ch if ch.is_ascii_alphabetic() => key.push(ch),
+            _ => {}
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    key
+}
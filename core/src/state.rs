@@ -0,0 +1,81 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! State shared by callbacks that may re-enter each other.
+//!
+//! Calls out to the host, like a COM call, can call back into the same object before they
+//! return. A `RefCell` borrow held across such a call panics on the re-entrant borrow, so
+//! [`StateCell`] only lends its state to a closure: values needed for a call out are copied
+//! or cloned out of the closure, and the state is free again by the time the call is made.
+
+use std::cell::RefCell;
+
+#[derive(Debug)]
+pub struct StateCell<T> {
+    value: RefCell<Option<T>>,
+}
+
+impl<T> Default for StateCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> StateCell<T> {
+    /// An empty cell.
+    pub const fn new() -> Self {
+        StateCell {
+            value: RefCell::new(None),
+        }
+    }
+
+    /// Runs `f` on the state.
+    ///
+    /// Returns `None` without running `f` if there is no state, or if it's being accessed
+    /// further up the stack, i.e. `f` made a call out that called back here.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let mut value = self.value.try_borrow_mut().ok()?;
+        value.as_mut().map(f)
+    }
+
+    /// Runs `f` on a value lent out of the state by `lend`, so that `f` can call out with it
+    /// without holding the state, then hands the value back with `restore`.
+    ///
+    /// Returns `None` without running `f` if there is no state, or if it's being accessed, as
+    /// [`with`](Self::with) does. The value is dropped if the state is gone by the time it's
+    /// handed back.
+    pub fn with_lent<V, R>(
+        &self,
+        lend: impl FnOnce(&mut T) -> V,
+        f: impl FnOnce(&V) -> R,
+        restore: impl FnOnce(&mut T, V),
+    ) -> Option<R> {
+        let value = self.with(lend)?;
+        let result = f(&value);
+        self.with(|state| restore(state, value));
+        Some(result)
+    }
+
+    /// Whether the state is being accessed further up the stack.
+    pub fn is_busy(&self) -> bool {
+        self.value.try_borrow_mut().is_err()
+    }
+
+    /// Sets the state, handing it back if the old one is being accessed.
+    ///
+    /// The old state is dropped after the cell is released.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        let Ok(mut current) = self.value.try_borrow_mut() else {
+            return Err(value);
+        };
+        let old = current.replace(value);
+        drop(current);
+        drop(old);
+        Ok(())
+    }
+
+    /// Takes the state out, unless it's being accessed.
+    pub fn take(&self) -> Option<T> {
+        self.value.try_borrow_mut().ok()?.take()
+    }
+}
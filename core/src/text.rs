@@ -0,0 +1,170 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::borrow::Cow;
+
+const HASANTA: char = '\u{09CD}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// A run of Bangla letters and signs.
+    Word(&'a str),
+    /// A run of anything else other than whitespace, e.g. punctuation, digits or Latin text.
+    Other(&'a str),
+}
+
+/// Returns `true` for characters making up Bangla words: letters, vowel signs and other marks
+/// of the Bengali block, along with the joiners used to shape conjuncts.
+pub fn is_word_char(ch: char) -> bool {
+    matches!(ch, '\u{0980}'..='\u{09E3}' | '\u{09F0}'..='\u{09F1}')
+        || matches!(ch, '\u{09FC}' | '\u{09FE}' | '\u{200C}' | '\u{200D}')
+}
+
+/// Splits `text` into words and other runs, skipping whitespace, along with their byte offsets.
+pub fn tokenize(text: &str) -> impl Iterator<Item = (usize, Token<'_>)> {
+    let mut rest = text;
+    let mut offset = 0;
+
+    std::iter::from_fn(move || {
+        let trimmed = rest.trim_start();
+        offset += rest.len() - trimmed.len();
+        rest = trimmed;
+
+        let is_word = is_word_char(rest.chars().next()?);
+        let end = rest
+            .find(|ch: char| ch.is_whitespace() || is_word_char(ch) != is_word)
+            .unwrap_or(rest.len());
+        let (token, tail) = rest.split_at(end);
+
+        let start = offset;
+        offset += end;
+        rest = tail;
+
+        Some((
+            start,
+            if is_word {
+                Token::Word(token)
+            } else {
+                Token::Other(token)
+            },
+        ))
+    })
+}
+
+/// Returns `true` for signs that attach to the preceding letter.
+fn is_combining(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{0981}'..='\u{0983}' | '\u{09BC}' | '\u{09BE}'..='\u{09CD}' | '\u{09D7}'
+    ) || matches!(
+        ch,
+        '\u{09E2}'..='\u{09E3}' | '\u{09FE}' | '\u{200C}' | '\u{200D}'
+    )
+}
+
+/// Splits `text` into user perceived characters, keeping conjuncts and their signs together.
+pub fn graphemes(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+
+    std::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let (_, mut prev) = chars.next()?;
+
+        let end = chars
+            .find(|&(_, ch)| {
+                let joined = is_combining(ch) || matches!(prev, HASANTA | '\u{200C}' | '\u{200D}');
+                prev = ch;
+                !joined
+            })
+            .map_or(rest.len(), |(i, _)| i);
+
+        let (grapheme, tail) = rest.split_at(end);
+        rest = tail;
+        Some(grapheme)
+    })
+}
+
+/// Brings Bangla text to its canonical form, where letters with a nukta are decomposed.
+///
+/// Input methods and word lists disagree on using the precomposed `ড়`, `ঢ়` and `য়`,
+/// so text has to be normalized before comparison.
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    const NUKTA: char = '\u{09BC}';
+
+    if !text.contains(['\u{09DC}', '\u{09DD}', '\u{09DF}']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut normalized = String::with_capacity(text.len() + 8);
+    for ch in text.chars() {
+        match ch {
+            '\u{09DC}' => normalized.extend(['\u{09A1}', NUKTA]),
+            '\u{09DD}' => normalized.extend(['\u{09A2}', NUKTA]),
+            '\u{09DF}' => normalized.extend(['\u{09AF}', NUKTA]),
+            ch => normalized.push(ch),
+        }
+    }
+    Cow::Owned(normalized)
+}
+
+/// The span of UTF-16 text replaced between two versions of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf16Diff {
+    /// Code units at the start shared by both versions.
+    pub start: usize,
+    /// Code units of the old version replaced after `start`.
+    pub old_len: usize,
+    /// Code units of the new version replacing them.
+    pub new_len: usize,
+}
+
+impl Utf16Diff {
+    pub fn is_empty(&self) -> bool {
+        self.old_len == 0 && self.new_len == 0
+    }
+}
+
+/// Finds the smallest span to replace in `old` to get `new`.
+///
+/// The span never splits a surrogate pair or a cluster, so a vowel sign or a conjunct is
+/// replaced along with the letter it attaches to.
+pub fn utf16_diff(old: &[u16], new: &[u16]) -> Utf16Diff {
+    let is_boundary =
+        |i: usize, j: usize| is_cluster_boundary(old, i) && is_cluster_boundary(new, j);
+
+    let mut prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    while !is_boundary(prefix, prefix) {
+        prefix -= 1;
+    }
+
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let mut suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !is_boundary(old.len() - suffix, new.len() - suffix) {
+        suffix -= 1;
+    }
+
+    Utf16Diff {
+        start: prefix,
+        old_len: old.len() - prefix - suffix,
+        new_len: new.len() - prefix - suffix,
+    }
+}
+
+/// Returns `false` within a surrogate pair and before a sign attaching to the preceding letter.
+fn is_cluster_boundary(text: &[u16], i: usize) -> bool {
+    let (Some(&before), Some(&after)) = (i.checked_sub(1).and_then(|i| text.get(i)), text.get(i))
+    else {
+        return true;
+    };
+    let as_char = |unit: u16| char::from_u32(unit.into());
+
+    !(0xDC00..=0xDFFF).contains(&after)
+        && !as_char(after).is_some_and(is_combining)
+        && !matches!(as_char(before), Some(HASANTA | '\u{200C}' | '\u{200D}'))
+}
@@ -0,0 +1,58 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::spell::SpellChecker;
+
+fn word_list(words: &str) -> SpellChecker {
+    let mut checker = SpellChecker::default();
+    checker.extend_from_str(words);
+    checker
+}
+
+fn alternates(checker: &SpellChecker, word: &str, roman: Option<&str>) -> Vec<String> {
+    checker
+        .alternates(word, roman, 10)
+        .into_iter()
+        .map(|alternate| alternate.word)
+        .collect()
+}
+
+#[test]
+fn offers_words_sounding_alike() {
+    let checker = word_list("কাল\nখাল\nকল\nকলা\n");
+    assert_eq!(alternates(&checker, "কাল", None), ["খাল"]);
+    assert_eq!(alternates(&checker, "খাল", None), ["কাল"]);
+    assert_eq!(alternates(&checker, "কলা", None), Vec::<String>::new());
+}
+
+#[test]
+fn ranks_closer_spellings_first() {
+    let checker = word_list("চিনি\nছীনী\nছিনি\nচীনি\n");
+    assert_eq!(alternates(&checker, "চিনি", None), ["চীনি", "ছিনি", "ছীনী"]);
+
+    let checker = word_list("সব\nষব\nশব\n");
+    assert_eq!(alternates(&checker, "সব", None), ["শব", "ষব"]);
+}
+
+#[test]
+fn offers_words_read_as_typed() {
+    let checker = word_list("কাল\nখাল\nকল\n");
+    assert_eq!(alternates(&checker, "কাল", Some("kol")), ["কল", "খাল"]);
+    assert_eq!(alternates(&checker, "কাল", Some("khal")), ["খাল"]);
+}
+
+#[test]
+fn offers_alternates_for_unknown_words() {
+    let checker = word_list("খাল\nকল\n");
+    assert!(!checker.is_correct("কাল"));
+    assert_eq!(alternates(&checker, "কাল", None), ["খাল"]);
+}
+
+#[test]
+fn limits_the_alternates() {
+    let checker = word_list("সব\nষব\nশব\n");
+    let alternates = checker.alternates("সব", None, 1);
+    assert_eq!(alternates.len(), 1);
+    assert_eq!(alternates[0].word, "শব");
+    assert_eq!(alternates[0].distance, 1);
+}
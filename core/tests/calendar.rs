@@ -0,0 +1,227 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::calendar::{
+    BangabdaDate, Calendar, Date, DateFormat, REVISION_YEAR, Time, day_with_suffix, is_leap_year,
+};
+
+fn date(year: i32, month: u8, day: u8) -> Date {
+    Date::new(year, month, day).unwrap()
+}
+
+fn bangabda(year: i32, month: u8, day: u8) -> BangabdaDate {
+    BangabdaDate::new(year, month, day).unwrap()
+}
+
+#[test]
+fn gregorian_leap_years() {
+    assert!(is_leap_year(2024));
+    assert!(is_leap_year(2000));
+    assert!(!is_leap_year(1900));
+    assert!(!is_leap_year(2026));
+    assert_eq!(Date::new(2024, 2, 29), Some(date(2024, 2, 29)));
+    assert_eq!(Date::new(2023, 2, 29), None);
+    assert_eq!(Date::new(2023, 4, 31), None);
+    assert_eq!(Date::new(2023, 13, 1), None);
+    assert_eq!(Date::new(2023, 1, 0), None);
+}
+
+#[test]
+fn counts_days() {
+    assert_eq!(date(1970, 1, 1).to_days(), 0);
+    assert_eq!(date(1969, 12, 31).to_days(), -1);
+    assert_eq!(date(2000, 3, 1).to_days(), 11_017);
+
+    let mut expected = date(1899, 12, 31);
+    for days in date(1900, 1, 1).to_days()..date(2101, 1, 1).to_days() {
+        let date = Date::from_days(days);
+        assert_eq!(date.to_days(), days);
+        assert!(date > expected);
+        assert!(Date::new(date.year, date.month, date.day).is_some());
+        expected = date;
+    }
+}
+
+#[test]
+fn weekdays() {
+    assert_eq!(date(1970, 1, 1).weekday(), 4);
+    assert_eq!(date(1971, 12, 16).weekday(), 4);
+    assert_eq!(date(2000, 1, 1).weekday(), 6);
+    assert_eq!(date(2026, 10, 19).weekday(), 1);
+}
+
+#[test]
+fn bangla_new_year() {
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2026, 4, 14)),
+        bangabda(1433, 1, 1)
+    );
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2026, 4, 13)),
+        bangabda(1432, 12, 30)
+    );
+    assert_eq!(bangabda(1426, 1, 1).to_gregorian(), date(2019, 4, 14));
+}
+
+#[test]
+fn revised_calendar() {
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2026, 10, 19)),
+        bangabda(1433, 7, 3)
+    );
+
+    // The revision keeps days of national significance on the same Bangabda dates.
+    for year in 2020..2040 {
+        assert_eq!(
+            BangabdaDate::from_gregorian(date(year, 2, 21)),
+            bangabda(year - 594, 11, 8)
+        );
+        assert_eq!(
+            BangabdaDate::from_gregorian(date(year, 3, 26)),
+            bangabda(year - 594, 12, 12)
+        );
+        assert_eq!(
+            BangabdaDate::from_gregorian(date(year, 12, 16)),
+            bangabda(year - 593, 9, 1)
+        );
+    }
+}
+
+#[test]
+fn calendar_before_the_revision() {
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2016, 2, 21)),
+        bangabda(1422, 11, 9)
+    );
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2018, 12, 16)),
+        bangabda(1425, 9, 2)
+    );
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2019, 4, 13)),
+        bangabda(1425, 12, 30)
+    );
+    assert_eq!(BangabdaDate::month_len(REVISION_YEAR - 1, 6), 30);
+    assert_eq!(BangabdaDate::month_len(REVISION_YEAR, 6), 31);
+}
+
+#[test]
+fn leap_falgun() {
+    // ফাল্গুন 1430 falls in 2024.
+    assert!(BangabdaDate::is_leap_year(1430));
+    assert!(!BangabdaDate::is_leap_year(1429));
+    assert_eq!(BangabdaDate::month_len(1430, 11), 30);
+    assert_eq!(BangabdaDate::month_len(1429, 11), 29);
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2024, 2, 29)),
+        bangabda(1430, 11, 16)
+    );
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2024, 3, 14)),
+        bangabda(1430, 11, 30)
+    );
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2023, 3, 14)),
+        bangabda(1429, 11, 29)
+    );
+    assert_eq!(BangabdaDate::new(1429, 11, 30), None);
+
+    // Before the revision, a leap ফাল্গুন had 31 days.
+    assert_eq!(BangabdaDate::month_len(1422, 11), 31);
+    assert_eq!(
+        BangabdaDate::from_gregorian(date(2016, 3, 14)),
+        bangabda(1422, 11, 31)
+    );
+}
+
+#[test]
+fn converts_every_day_both_ways() {
+    let mut previous = BangabdaDate::from_gregorian(date(1989, 12, 31));
+    for days in date(1990, 1, 1).to_days()..date(2101, 1, 1).to_days() {
+        let gregorian = Date::from_days(days);
+        let converted = BangabdaDate::from_gregorian(gregorian);
+        assert_eq!(converted.to_gregorian(), gregorian);
+        assert!(BangabdaDate::new(converted.year, converted.month, converted.day).is_some());
+
+        // Days follow one another, each month running to its end.
+        let next_day = BangabdaDate::new(previous.year, previous.month, previous.day + 1);
+        let next_month = BangabdaDate::new(previous.year, previous.month + 1, 1);
+        let next_year = bangabda(previous.year + 1, 1, 1);
+        assert_eq!(converted, next_day.or(next_month).unwrap_or(next_year));
+        previous = converted;
+    }
+}
+
+#[test]
+fn year_lengths() {
+    for year in 1400..1500 {
+        let len = (1..=12)
+            .map(|month| u32::from(BangabdaDate::month_len(year, month)))
+            .sum::<u32>();
+        let expected = if BangabdaDate::is_leap_year(year) {
+            366
+        } else {
+            365
+        };
+        assert_eq!(len, expected, "{year}");
+    }
+}
+
+#[test]
+fn day_suffixes() {
+    assert_eq!(day_with_suffix(1), "১লা");
+    assert_eq!(day_with_suffix(2), "২রা");
+    assert_eq!(day_with_suffix(3), "৩রা");
+    assert_eq!(day_with_suffix(4), "৪ঠা");
+    assert_eq!(day_with_suffix(5), "৫ই");
+    assert_eq!(day_with_suffix(18), "১৮ই");
+    assert_eq!(day_with_suffix(19), "১৯শে");
+    assert_eq!(day_with_suffix(31), "৩১শে");
+}
+
+#[test]
+fn parses_formats() {
+    assert_eq!(
+        DateFormat::parse(" bangabda: %d %B %Y "),
+        DateFormat {
+            calendar: Calendar::Bangabda,
+            pattern: "%d %B %Y".to_owned()
+        }
+    );
+    assert_eq!(
+        DateFormat::parse("%H:%M"),
+        DateFormat {
+            calendar: Calendar::Gregorian,
+            pattern: "%H:%M".to_owned()
+        }
+    );
+    assert_eq!(DateFormat::parse("gregorian:%d"), DateFormat::parse("%d"));
+}
+
+#[test]
+fn formats_dates() {
+    let today = date(2026, 10, 19);
+    let time = Time { hour: 9, minute: 5 };
+    let format = |text: &str| DateFormat::parse(text).format(today, time);
+
+    assert_eq!(format("bangabda:%d %B %Y"), "৩ কার্তিক ১৪৩৩");
+    assert_eq!(format("bangabda:%o %B"), "৩রা কার্তিক");
+    assert_eq!(format("%d %B %Y"), "১৯ অক্টোবর ২০২৬");
+    assert_eq!(format("%A, %d/%m/%Y"), "সোমবার, ১৯/১০/২০২৬");
+    assert_eq!(format("%p %I:%M"), "সকাল ৯:০৫");
+    assert_eq!(format("%H:%M"), "০৯:০৫");
+    assert_eq!(format("100%% %q %"), "100% %q %");
+}
+
+#[test]
+fn formats_times() {
+    let format = |hour, minute| {
+        DateFormat::parse("%p %I:%M").format(date(2026, 1, 1), Time { hour, minute })
+    };
+    assert_eq!(format(0, 0), "রাত ১২:০০");
+    assert_eq!(format(4, 30), "ভোর ৪:৩০");
+    assert_eq!(format(12, 15), "দুপুর ১২:১৫");
+    assert_eq!(format(16, 0), "বিকাল ৪:০০");
+    assert_eq!(format(18, 45), "সন্ধ্যা ৬:৪৫");
+    assert_eq!(format(23, 59), "রাত ১১:৫৯");
+}
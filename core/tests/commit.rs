@@ -0,0 +1,68 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::commit::{CommitAction, CommitPolicy, Terminator};
+
+#[test]
+fn default_policy() {
+    let policy = CommitPolicy::default();
+
+    for terminator in [Terminator::Space, Terminator::Tab, Terminator::Navigation] {
+        assert_eq!(policy.action(terminator), CommitAction::CommitAndForward);
+    }
+    assert_eq!(policy.action(Terminator::Enter), CommitAction::Commit);
+    assert_eq!(policy.action(Terminator::Escape), CommitAction::Cancel);
+    assert_eq!(policy.action(Terminator::FocusLoss), CommitAction::Commit);
+}
+
+#[test]
+fn actions() {
+    assert!(CommitAction::CommitAndForward.commits());
+    assert!(CommitAction::CommitAndForward.forwards_key());
+    assert!(CommitAction::Commit.commits());
+    assert!(!CommitAction::Commit.forwards_key());
+    assert!(!CommitAction::Cancel.commits());
+    assert!(!CommitAction::Cancel.forwards_key());
+}
+
+#[test]
+fn set_by_name() {
+    let mut policy = CommitPolicy::default();
+
+    assert!(policy.set_by_name("focus_loss", "cancel"));
+    assert_eq!(policy.action(Terminator::FocusLoss), CommitAction::Cancel);
+
+    assert!(policy.set_by_name("enter", "commit_and_forward"));
+    assert_eq!(
+        policy.action(Terminator::Enter),
+        CommitAction::CommitAndForward
+    );
+
+    assert!(!policy.set_by_name("backspace", "cancel"));
+    assert!(!policy.set_by_name("space", "forward"));
+    assert_eq!(
+        policy.action(Terminator::Space),
+        CommitAction::CommitAndForward
+    );
+}
+
+#[test]
+fn focus_loss_never_forwards() {
+    let mut policy = CommitPolicy::default();
+    policy.set(Terminator::FocusLoss, CommitAction::CommitAndForward);
+    assert_eq!(policy.action(Terminator::FocusLoss), CommitAction::Commit);
+}
+
+#[test]
+fn names_round_trip() {
+    for terminator in Terminator::ALL {
+        assert_eq!(Terminator::from_name(terminator.name()), Some(terminator));
+    }
+    for action in [
+        CommitAction::CommitAndForward,
+        CommitAction::Commit,
+        CommitAction::Cancel,
+    ] {
+        assert_eq!(CommitAction::from_name(action.name()), Some(action));
+    }
+}
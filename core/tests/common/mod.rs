@@ -0,0 +1,18 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Helpers shared by the integration tests.
+
+use okkhor::{khipro::KhiproPhonetic, parser::Parser};
+
+/// Converts with the Avro phonetic transcriber.
+pub fn avro() -> impl FnMut(&str, &mut String) {
+    let parser = Parser::new_phonetic();
+    move |input, output| parser.convert_into(input, output)
+}
+
+/// Converts with the Khipro transcriber.
+pub fn khipro() -> impl FnMut(&str, &mut String) {
+    let scribe = KhiproPhonetic::new();
+    move |input, output| scribe.convert_into(input, output)
+}
@@ -0,0 +1,120 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+mod common;
+
+use common::{avro, khipro};
+use uo_keyboard_core::{context::SeedTable, preedit::Preedit};
+
+/// Types `input` key by key after `left`, returning the final composition text.
+fn continue_word(left: &str, input: &str, mut convert: impl FnMut(&str, &mut String)) -> String {
+    let seeds = SeedTable::build(&mut convert);
+    let mut preedit = Preedit::new();
+    preedit.set_seed(seeds.seed_for(left).unwrap_or_default(), &mut convert);
+    for end in 1..=input.len() {
+        preedit.convert(&input[..end], &mut convert);
+    }
+    preedit.text().to_owned()
+}
+
+#[test]
+fn finds_seeds_for_consonants() {
+    let avro = SeedTable::build(avro());
+    assert_eq!(avro.seed_for("ক"), Some("k"));
+    assert_eq!(avro.seed_for("আমি খ"), Some("kh"));
+    assert_eq!(avro.seed_for("ক্ক"), Some("k"));
+    assert_eq!(avro.seed_for("কি"), None);
+    assert_eq!(avro.seed_for("ক "), None);
+    assert_eq!(avro.seed_for("abc"), None);
+    assert_eq!(avro.seed_for(""), None);
+
+    let khipro = SeedTable::build(khipro());
+    assert_eq!(khipro.seed_for("ক"), Some("k"));
+    assert_eq!(khipro.seed_for("য়"), Some("y"));
+}
+
+#[test]
+fn matches_nukta_letters_either_way() {
+    let avro = SeedTable::build(avro());
+    assert_eq!(avro.seed_for("\u{09DC}"), Some("R"));
+    assert_eq!(avro.seed_for("\u{09A1}\u{09BC}"), Some("R"));
+}
+
+#[test]
+fn vowel_after_consonant_becomes_a_sign() {
+    assert_eq!(continue_word("ক", "i", avro()), "ি");
+    assert_eq!(continue_word("ক", "i", khipro()), "ি");
+    assert_eq!(continue_word("আমার ক", "a", avro()), "া");
+    assert_eq!(continue_word("ক", "I", avro()), "ী");
+}
+
+#[test]
+fn consonant_after_consonant_joins_it() {
+    assert_eq!(continue_word("ক", "t", avro()), "্ত");
+    assert_eq!(continue_word("ক", "ri", khipro()), "্রি");
+}
+
+#[test]
+fn no_context_converts_as_is() {
+    assert_eq!(continue_word("", "i", avro()), "ই");
+    assert_eq!(continue_word("কি", "i", avro()), "ই");
+    assert_eq!(continue_word("ক ", "i", khipro()), "ই");
+}
+
+#[test]
+fn context_only_reaches_the_first_word() {
+    let mut plain = String::new();
+    avro()("ami", &mut plain);
+    assert_eq!(continue_word("ক", "i ami", avro()), format!("ি {plain}"));
+}
+
+#[test]
+fn unsurviving_seed_drops_the_context() {
+    // Khipro reads `kh` as `খ`, which can't be put after the `ক` already there.
+    let mut plain = String::new();
+    khipro()("h", &mut plain);
+    assert_eq!(continue_word("ক", "h", khipro()), plain);
+}
+
+#[test]
+fn seeded_incremental_matches_full_conversion() {
+    let mut convert = avro();
+    let mut seed_output = String::new();
+    convert("k", &mut seed_output);
+
+    let input = "rishna bhalo achhe";
+    let mut preedit = Preedit::new();
+    preedit.set_seed("k", &mut convert);
+    for end in 1..=input.len() {
+        preedit.convert(&input[..end], &mut convert);
+
+        let (first, rest) = input[..end]
+            .split_once(' ')
+            .map_or((&input[..end], ""), |(first, rest)| (first, rest));
+        let mut expected = String::new();
+        convert(&format!("k{first}"), &mut expected);
+        let mut expected = expected
+            .strip_prefix(seed_output.as_str())
+            .unwrap()
+            .to_owned();
+        if end > first.len() {
+            let mut converted = String::new();
+            convert(&format!(" {rest}"), &mut converted);
+            expected.push_str(&converted);
+        }
+        assert_eq!(preedit.text(), expected, "{}", &input[..end]);
+    }
+}
+
+#[test]
+fn clearing_drops_the_seed() {
+    let mut convert = avro();
+    let mut preedit = Preedit::new();
+    preedit.set_seed("k", &mut convert);
+    preedit.convert("i", &mut convert);
+    assert_eq!(preedit.text(), "ি");
+
+    preedit.clear();
+    preedit.convert("i", &mut convert);
+    assert_eq!(preedit.text(), "ই");
+}
@@ -0,0 +1,197 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use okkhor::{khipro::KhiproPhonetic, parser::Parser};
+use uo_keyboard_core::direct::{DirectInput, Replacement};
+
+/// A document without compositions: plain text and a caret.
+#[derive(Debug, Default)]
+struct MockDocument {
+    text: Vec<u16>,
+    caret: usize,
+}
+
+impl MockDocument {
+    fn new(text: &str) -> Self {
+        let text = text.encode_utf16().collect::<Vec<_>>();
+        MockDocument {
+            caret: text.len(),
+            text,
+        }
+    }
+
+    fn apply(&mut self, replacement: Replacement) {
+        let start = self.caret - replacement.delete;
+        self.text
+            .splice(start..self.caret, replacement.insert.iter().copied());
+        self.caret = start + replacement.insert.len();
+    }
+
+    /// Types a character as an application without IME would.
+    fn type_char(&mut self, ch: char) {
+        let mut buffer = [0; 2];
+        let units = ch.encode_utf16(&mut buffer);
+        self.text
+            .splice(self.caret..self.caret, units.iter().copied());
+        self.caret += units.len();
+    }
+
+    fn left(&self, len: usize) -> String {
+        String::from_utf16_lossy(&self.text[self.caret.saturating_sub(len)..self.caret])
+    }
+
+    fn text(&self) -> String {
+        String::from_utf16_lossy(&self.text)
+    }
+}
+
+/// Types `roman` into `doc` as the IME does without a composition, word by word.
+fn type_words(
+    doc: &mut MockDocument,
+    direct: &mut DirectInput,
+    roman: &str,
+    adapt: impl Fn(char) -> char,
+    mut convert: impl FnMut(&str, &mut String),
+) {
+    for ch in roman.chars() {
+        if ch == ' ' {
+            direct.finish();
+            doc.type_char(ch);
+            continue;
+        }
+        if !direct.is_in_place(&doc.left(direct.context_len())) {
+            direct.clear();
+        }
+        let replacement = direct.push(ch, adapt(ch), &mut convert);
+        doc.apply(replacement);
+    }
+}
+
+fn plain(input: &str, mut convert: impl FnMut(&str, &mut String)) -> String {
+    let mut output = String::new();
+    convert(input, &mut output);
+    output
+}
+
+fn check_typing<F: FnMut(&str, &mut String)>(
+    convert: impl Fn() -> F,
+    adapt: impl Fn(char) -> char + Copy,
+) {
+    let mut doc = MockDocument::new("লেখা: ");
+    let mut direct = DirectInput::new();
+    type_words(
+        &mut doc,
+        &mut direct,
+        "ami banglay gan gai",
+        adapt,
+        convert(),
+    );
+
+    let expected = ["ami", "banglay", "gan", "gai"]
+        .map(|word| plain(word, convert()))
+        .join(" ");
+    assert_eq!(doc.text(), format!("লেখা: {expected}"));
+    assert_eq!(direct.text(), plain("gai", convert()));
+}
+
+#[test]
+fn replaces_the_previous_output_in_avro() {
+    let parser = Parser::new_phonetic();
+    check_typing(
+        || |input: &str, output: &mut String| parser.convert_into(input, output),
+        |ch| ch,
+    );
+}
+
+#[test]
+fn replaces_the_previous_output_in_khipro() {
+    let scribe = KhiproPhonetic::new();
+    check_typing(
+        || |input: &str, output: &mut String| scribe.convert_into(input, output),
+        |ch| ch.to_ascii_lowercase(),
+    );
+}
+
+#[test]
+fn replaces_only_the_changed_tail() {
+    let parser = Parser::new_phonetic();
+    let mut convert = |input: &str, output: &mut String| parser.convert_into(input, output);
+    let mut direct = DirectInput::new();
+
+    direct.push('a', 'a', &mut convert);
+    direct.push('m', 'm', &mut convert);
+    // The vowel sign of `i` joins the last letter, which is replaced along with it.
+    let replacement = direct.push('i', 'i', &mut convert);
+    assert_eq!(replacement.delete, 1);
+    assert_eq!(String::from_utf16_lossy(replacement.insert), "মি");
+}
+
+#[test]
+fn backspace_converts_the_shorter_input() {
+    let parser = Parser::new_phonetic();
+    let mut convert = |input: &str, output: &mut String| parser.convert_into(input, output);
+    let mut doc = MockDocument::new("");
+    let mut direct = DirectInput::new();
+    type_words(&mut doc, &mut direct, "ami", |ch| ch, &mut convert);
+
+    doc.apply(direct.pop(&mut convert).unwrap());
+    assert_eq!(doc.text(), plain("am", &mut convert));
+    doc.apply(direct.pop(&mut convert).unwrap());
+    doc.apply(direct.pop(&mut convert).unwrap());
+    assert_eq!(doc.text(), "");
+    assert!(direct.is_empty());
+    assert_eq!(direct.pop(&mut convert), None);
+}
+
+#[test]
+fn moved_caret_starts_a_new_input() {
+    let parser = Parser::new_phonetic();
+    let mut convert = |input: &str, output: &mut String| parser.convert_into(input, output);
+    let mut doc = MockDocument::new("");
+    let mut direct = DirectInput::new();
+    type_words(&mut doc, &mut direct, "ami", |ch| ch, &mut convert);
+
+    // The user clicks to the start of the document.
+    doc.caret = 0;
+    assert!(!direct.is_in_place(&doc.left(direct.context_len())));
+    type_words(&mut doc, &mut direct, "o", |ch| ch, &mut convert);
+    assert_eq!(
+        doc.text(),
+        format!("{}{}", plain("o", &mut convert), plain("ami", &mut convert))
+    );
+}
+
+#[test]
+fn revert_and_cancel_replace_the_whole_text() {
+    let parser = Parser::new_phonetic();
+    let mut convert = |input: &str, output: &mut String| parser.convert_into(input, output);
+    let mut doc = MockDocument::new("");
+    let mut direct = DirectInput::new();
+
+    type_words(&mut doc, &mut direct, "Hello", |ch| ch, &mut convert);
+    doc.apply(direct.revert());
+    assert_eq!(doc.text(), "Hello");
+    assert!(direct.is_empty());
+
+    doc.type_char(' ');
+    type_words(&mut doc, &mut direct, "ami", |ch| ch, &mut convert);
+    doc.apply(direct.cancel());
+    assert_eq!(doc.text(), "Hello ");
+    assert!(direct.is_empty());
+}
+
+#[test]
+fn resumes_committed_text() {
+    let parser = Parser::new_phonetic();
+    let mut convert = |input: &str, output: &mut String| parser.convert_into(input, output);
+    let ami = plain("ami", &mut convert);
+    let mut doc = MockDocument::new(&format!("{ami} "));
+    let mut direct = DirectInput::new();
+
+    direct.resume("ami ", "ami ", &format!("{ami} "));
+    assert!(direct.is_in_place(&doc.left(direct.context_len())));
+    doc.apply(direct.pop(&mut convert).unwrap());
+    assert_eq!(doc.text(), ami);
+    doc.apply(direct.push('k', 'k', &mut convert));
+    assert_eq!(doc.text(), plain("amik", &mut convert));
+}
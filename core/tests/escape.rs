@@ -0,0 +1,172 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+mod common;
+
+use common::{avro, khipro};
+use uo_keyboard_core::{
+    escape::{Escapes, Segment},
+    preedit::Preedit,
+};
+
+fn braces() -> Escapes {
+    Escapes::new("{", "}").unwrap()
+}
+
+fn plain(input: &str, mut convert: impl FnMut(&str, &mut String)) -> String {
+    let mut output = String::new();
+    convert(input, &mut output);
+    output
+}
+
+/// Types `input` key by key, returning the final composition text.
+fn compose(
+    escapes: Option<Escapes>,
+    input: &str,
+    mut convert: impl FnMut(&str, &mut String),
+) -> String {
+    let mut preedit = Preedit::new();
+    preedit.set_escapes(escapes);
+    for (end, _) in input.char_indices().skip(1) {
+        preedit.convert(&input[..end], &mut convert);
+    }
+    preedit.convert(input, &mut convert);
+    preedit.text().to_owned()
+}
+
+#[test]
+fn splits_segments() {
+    let escapes = braces();
+    assert_eq!(
+        escapes.segments("ami {iPhone} kini").collect::<Vec<_>>(),
+        [
+            Segment::Convert("ami "),
+            Segment::Literal("iPhone"),
+            Segment::Convert(" kini"),
+        ]
+    );
+    assert_eq!(
+        escapes.segments("{}").collect::<Vec<_>>(),
+        [Segment::Literal("")]
+    );
+    assert_eq!(escapes.segments("").count(), 0);
+}
+
+#[test]
+fn nests_delimiters() {
+    let escapes = braces();
+    assert_eq!(
+        escapes.segments("{f(){}}x").collect::<Vec<_>>(),
+        [Segment::Literal("f(){}"), Segment::Convert("x")]
+    );
+    assert_eq!(
+        escapes.segments("{a{b}c}{d}").collect::<Vec<_>>(),
+        [Segment::Literal("a{b}c"), Segment::Literal("d")]
+    );
+    // A closing delimiter outside of a segment is transcribed like any other symbol.
+    assert_eq!(
+        escapes.segments("a}b").collect::<Vec<_>>(),
+        [Segment::Convert("a}b")]
+    );
+}
+
+#[test]
+fn unterminated_segments_run_to_the_end() {
+    let escapes = braces();
+    assert_eq!(
+        escapes.segments("ami {Hello wo").collect::<Vec<_>>(),
+        [Segment::Convert("ami "), Segment::Unterminated("Hello wo")]
+    );
+    assert_eq!(
+        escapes.segments("{a{b}c").collect::<Vec<_>>(),
+        [Segment::Unterminated("a{b}c")]
+    );
+    assert!(escapes.is_open("ami {"));
+    assert!(escapes.is_open("{a{b}"));
+    assert!(!escapes.is_open("{a}"));
+    assert!(!escapes.is_open("ami"));
+}
+
+#[test]
+fn same_delimiters_dont_nest() {
+    let escapes = Escapes::new("|", "|").unwrap();
+    assert_eq!(
+        escapes.segments("a|b|c|d").collect::<Vec<_>>(),
+        [
+            Segment::Convert("a"),
+            Segment::Literal("b"),
+            Segment::Convert("c"),
+            Segment::Unterminated("d"),
+        ]
+    );
+}
+
+#[test]
+fn multi_character_delimiters() {
+    let escapes = Escapes::new("[[", "]]").unwrap();
+    assert_eq!(
+        escapes.segments("a[[x[y]]b").collect::<Vec<_>>(),
+        [
+            Segment::Convert("a"),
+            Segment::Literal("x[y"),
+            Segment::Convert("b"),
+        ]
+    );
+    assert_eq!(Escapes::new("", "]]"), None);
+}
+
+#[test]
+fn words_end_at_spaces_outside_of_segments() {
+    let escapes = braces();
+    assert_eq!(escapes.word_len("ami tumi"), Some(4));
+    assert_eq!(escapes.word_len("{a b} c"), Some(6));
+    assert_eq!(escapes.word_len("x{a b "), None);
+    assert_eq!(escapes.word_len("ami"), None);
+}
+
+/// Checks a scheme, with `convert` making its transcriber.
+fn keeps_literal_segments<F: FnMut(&str, &mut String)>(convert: impl Fn() -> F) {
+    assert_eq!(
+        compose(Some(braces()), "ami {iPhone 15} kini", convert()),
+        format!(
+            "{}iPhone 15{}",
+            plain("ami ", convert()),
+            plain(" kini", convert())
+        )
+    );
+    assert_eq!(compose(Some(braces()), "{a{b}c}", convert()), "a{b}c");
+    assert_eq!(
+        compose(Some(braces()), "bhalo {fn main() {", convert()),
+        format!("{}fn main() {{", plain("bhalo ", convert()))
+    );
+}
+
+#[test]
+fn keeps_literal_segments_in_avro() {
+    keeps_literal_segments(avro);
+}
+
+#[test]
+fn keeps_literal_segments_in_khipro() {
+    keeps_literal_segments(khipro);
+}
+
+#[test]
+fn no_escapes_transcribe_everything() {
+    assert_eq!(compose(None, "{ami}", avro()), plain("{ami}", avro()));
+}
+
+#[test]
+fn incremental_matches_full_conversion() {
+    let input = "ami {a b {c} d} tumi {e f";
+    let escapes = braces();
+    let mut convert = avro();
+    let mut preedit = Preedit::new();
+    preedit.set_escapes(Some(escapes.clone()));
+    for end in 1..=input.len() {
+        preedit.convert(&input[..end], &mut convert);
+        let mut expected = String::new();
+        escapes.transcribe(&input[..end], &mut expected, &mut convert);
+        assert_eq!(preedit.text(), expected, "{}", &input[..end]);
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::hotkey::Hotkey;
+
+fn hotkey(key: u16, ctrl: bool, alt: bool, shift: bool) -> Option<Hotkey> {
+    Some(Hotkey {
+        key,
+        ctrl,
+        alt,
+        shift,
+    })
+}
+
+#[test]
+fn parses_keys() {
+    assert_eq!(Hotkey::parse("z"), hotkey(b'Z'.into(), false, false, false));
+    assert_eq!(Hotkey::parse("7"), hotkey(b'7'.into(), false, false, false));
+    assert_eq!(Hotkey::parse("escape"), hotkey(0x1B, false, false, false));
+    assert_eq!(Hotkey::parse("Esc"), hotkey(0x1B, false, false, false));
+    assert_eq!(Hotkey::parse("f"), hotkey(b'F'.into(), false, false, false));
+    assert_eq!(Hotkey::parse("F12"), hotkey(0x7B, false, false, false));
+}
+
+#[test]
+fn parses_modifiers() {
+    assert_eq!(
+        Hotkey::parse("ctrl+z"),
+        hotkey(b'Z'.into(), true, false, false)
+    );
+    assert_eq!(
+        Hotkey::parse(" Control + Shift + Z "),
+        hotkey(b'Z'.into(), true, false, true)
+    );
+    assert_eq!(
+        Hotkey::parse("alt+backspace"),
+        hotkey(0x08, false, true, false)
+    );
+}
+
+#[test]
+fn rejects_unknown_keys() {
+    assert_eq!(Hotkey::parse(""), None);
+    assert_eq!(Hotkey::parse("ctrl+"), None);
+    assert_eq!(Hotkey::parse("ctrl"), None);
+    assert_eq!(Hotkey::parse("hyper+z"), None);
+    assert_eq!(Hotkey::parse("f25"), None);
+    assert_eq!(Hotkey::parse("zz"), None);
+    assert_eq!(Hotkey::parse("ctrl+z+x"), None);
+}
+
+#[test]
+fn matches_exact_modifiers() {
+    let undo = Hotkey::parse("ctrl+z").unwrap();
+    assert!(undo.matches(b'Z'.into(), true, false, false));
+    assert!(!undo.matches(b'Z'.into(), true, false, true));
+    assert!(!undo.matches(b'Z'.into(), false, false, false));
+    assert!(!undo.matches(b'Y'.into(), true, false, false));
+}
@@ -0,0 +1,99 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use okkhor::{khipro::KhiproPhonetic, parser::Parser};
+use uo_keyboard_core::incremental::IncrementalConverter;
+
+const AVRO_TEXT: &str = "ami banglay gan gai. amar sOnar bangla, ami tomay bhalobasi  \
+    chirodin tOmar akash, tOmar batash amar prane O ma amar prane bajay bNashi \
+    rri kkh ZNG t` o`";
+const KHIPRO_TEXT: &str = "ami banglay gan gai. amar sonar bangla, ami tomay valobasi  \
+    ciroding tomar akash, tomar batash amar prane o ma amar prane bajay bashi \
+    kSoma q;k ..";
+
+/// Types `text` a key at a time, checking each step against a full conversion.
+fn check_typing(text: &str, convert: impl Fn(&str, &mut String)) {
+    let mut converter = IncrementalConverter::new();
+    let mut expected = String::new();
+
+    for (end, _) in text.char_indices().skip(1).chain([(text.len(), ' ')]) {
+        let input = &text[..end];
+        convert(input, &mut expected);
+        assert_eq!(
+            converter.update(input, &convert),
+            expected,
+            "input: {input:?}"
+        );
+    }
+}
+
+/// Deletes `text` a key at a time, then edits it in the middle.
+fn check_editing(text: &str, convert: impl Fn(&str, &mut String)) {
+    let mut converter = IncrementalConverter::new();
+    let mut expected = String::new();
+    converter.update(text, &convert);
+
+    for (end, _) in text.char_indices().rev() {
+        let input = &text[..end];
+        convert(input, &mut expected);
+        assert_eq!(
+            converter.update(input, &convert),
+            expected,
+            "input: {input:?}"
+        );
+    }
+
+    converter.update(text, &convert);
+    for (at, _) in text.char_indices().step_by(7) {
+        for insertion in ["k", " ", "o "] {
+            let input = [&text[..at], insertion, &text[at..]].concat();
+            convert(&input, &mut expected);
+            assert_eq!(
+                converter.update(&input, &convert),
+                expected,
+                "input: {input:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn avro_matches_full_conversion() {
+    let parser = Parser::new_phonetic();
+    let convert = |input: &str, output: &mut String| parser.convert_into(input, output);
+
+    check_typing(AVRO_TEXT, convert);
+    check_editing(AVRO_TEXT, convert);
+}
+
+#[test]
+fn khipro_matches_full_conversion() {
+    let khipro = KhiproPhonetic::new();
+    let convert = |input: &str, output: &mut String| khipro.convert_into(input, output);
+
+    check_typing(KHIPRO_TEXT, convert);
+    check_editing(KHIPRO_TEXT, convert);
+}
+
+#[test]
+fn reuses_stable_segments() {
+    let mut converter = IncrementalConverter::new();
+    let mut converted = Vec::new();
+    let mut convert = |input: &str, output: &mut String| {
+        converted.push(input.to_owned());
+        output.clear();
+        output.push_str(&input.to_uppercase());
+    };
+
+    converter.update("ami tumi", &mut convert);
+    converter.update("ami tumi s", &mut convert);
+    converter.update("ami tomi s", &mut convert);
+    converter.update("", &mut convert);
+
+    assert_eq!(
+        converted,
+        ["ami ", "tumi", "tumi ", "s", "tomi ", "s"],
+        "only segments after an edit are converted again"
+    );
+    assert_eq!(converter.output(), "");
+}
@@ -0,0 +1,241 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Keys typed under several layouts, as `ToUnicodeEx` reports them, through to the text.
+
+use uo_keyboard_core::keys::{DeadKeys, KeyChars, KeyInput, transcribe_ascii};
+
+/// What `ToUnicodeEx` writes for a key: the code units and the count it returns.
+///
+/// After a dead key, it's what the layout makes of the dead key along with the next key.
+type Key = (&'static [u16], i32);
+
+const fn dead(key: &'static [u16]) -> Key {
+    (key, -1)
+}
+
+const A: Key = (&[0x61], 1);
+const B: Key = (&[0x62], 1);
+const E: Key = (&[0x65], 1);
+const K: Key = (&[0x6B], 1);
+
+mod us {
+    pub const AT: super::Key = (&[0x40], 1);
+    pub const F1: super::Key = (&[], 0);
+}
+
+mod us_international {
+    pub const APOSTROPHE: super::Key = super::dead(&[0x27]);
+    pub const QUOTE: super::Key = super::dead(&[0x22]);
+    /// `'` then `e`.
+    pub const APOSTROPHE_E: super::Key = (&[0xE9], 1);
+    /// `"` then `a`.
+    pub const QUOTE_A: super::Key = (&[0xE4], 1);
+    /// `'` then `b`, which the layout doesn't combine.
+    pub const APOSTROPHE_B: super::Key = (&[0x27, 0x62], 2);
+}
+
+mod german {
+    pub const SHARP_S: super::Key = (&[0xDF], 1);
+    pub const A_UMLAUT: super::Key = (&[0xE4], 1);
+    pub const ACUTE: super::Key = super::dead(&[0xB4]);
+    /// `´` then `e`.
+    pub const ACUTE_E: super::Key = (&[0xE9], 1);
+    /// `´` then `a`.
+    pub const ACUTE_A: super::Key = (&[0xE1], 1);
+    /// `´` then `k`, which the layout doesn't combine.
+    pub const ACUTE_K: super::Key = (&[0xB4, 0x6B], 2);
+    /// `´` then itself.
+    pub const ACUTE_ACUTE: super::Key = (&[0xB4, 0xB4], 2);
+}
+
+mod french {
+    pub const E_ACUTE: super::Key = (&[0xE9], 1);
+    pub const E_GRAVE: super::Key = (&[0xE8], 1);
+    pub const CIRCUMFLEX: super::Key = super::dead(&[0x5E]);
+    pub const DIAERESIS: super::Key = super::dead(&[0xA8]);
+    /// `^` then `w`.
+    pub const CIRCUMFLEX_W: super::Key = (&[0x175], 1);
+    /// `^` then a space.
+    pub const CIRCUMFLEX_SPACE: super::Key = (&[0x5E], 1);
+    /// `¨` then `i`.
+    pub const DIAERESIS_I: super::Key = (&[0xEF], 1);
+}
+
+mod spanish {
+    pub const N_TILDE: super::Key = (&[0xF1], 1);
+    pub const ACUTE: super::Key = super::dead(&[0xB4]);
+    /// `´` then `n`.
+    pub const ACUTE_N: super::Key = (&[0x144], 1);
+}
+
+/// Keys of layouts with chained dead keys, where a dead key can follow another.
+mod vietnamese {
+    pub const CIRCUMFLEX: super::Key = super::dead(&[0x5E]);
+    pub const ACUTE: super::Key = super::dead(&[0xB4]);
+    /// `^` then `´` then `e`.
+    pub const CIRCUMFLEX_ACUTE_E: super::Key = (&[0x1EBF], 1);
+}
+
+/// Keys of custom layouts writing more than one code unit.
+mod custom {
+    /// `𝔸`, outside of the BMP.
+    pub const DOUBLE_STRUCK_A: super::Key = (&[0xD835, 0xDD38], 2);
+    /// A ligature key typing two letters.
+    pub const LIGATURE: super::Key = (&[0x6B, 0x68], 2);
+    pub const LONE_SURROGATE: super::Key = (&[0xD835], 1);
+    pub const OVERLONG: super::Key = (&[0x61, 0x62, 0x63, 0x64, 0x65, 0x66], 6);
+}
+
+/// Types `keys`, returning the characters reaching the IME.
+fn type_keys(keys: &[Key]) -> String {
+    let mut dead_keys = DeadKeys::new();
+    let mut typed = String::new();
+    for &(units, count) in keys {
+        if let KeyInput::Chars(chars) = dead_keys.feed(KeyInput::decode(units, count)) {
+            typed.extend(chars.as_slice());
+        }
+    }
+    typed
+}
+
+#[test]
+fn decodes_single_characters() {
+    assert_eq!(
+        KeyInput::decode(german::SHARP_S.0, german::SHARP_S.1),
+        KeyInput::Chars(['ß'].into_iter().collect())
+    );
+    assert_eq!(KeyInput::decode(us::F1.0, us::F1.1), KeyInput::None);
+    assert_eq!(
+        KeyInput::decode(german::ACUTE.0, german::ACUTE.1),
+        KeyInput::Dead('´')
+    );
+}
+
+#[test]
+fn decodes_multiple_units() {
+    assert_eq!(type_keys(&[custom::DOUBLE_STRUCK_A]), "𝔸");
+    assert_eq!(type_keys(&[custom::LIGATURE]), "kh");
+    assert_eq!(type_keys(&[custom::LONE_SURROGATE]), "");
+    assert_eq!(type_keys(&[custom::OVERLONG]), "abcd");
+}
+
+#[test]
+fn keeps_layout_characters() {
+    assert_eq!(type_keys(&[us::AT, A]), "@a");
+    assert_eq!(type_keys(&[german::SHARP_S, german::A_UMLAUT]), "ßä");
+    assert_eq!(type_keys(&[french::E_ACUTE, french::E_GRAVE]), "éè");
+    assert_eq!(type_keys(&[A, spanish::N_TILDE, A]), "aña");
+}
+
+#[test]
+fn types_what_the_layout_composes() {
+    assert_eq!(type_keys(&[german::ACUTE, german::ACUTE_E]), "é");
+    assert_eq!(type_keys(&[french::DIAERESIS, french::DIAERESIS_I]), "ï");
+    assert_eq!(type_keys(&[spanish::ACUTE, spanish::ACUTE_N]), "ń");
+    assert_eq!(
+        type_keys(&[us_international::APOSTROPHE, us_international::APOSTROPHE_E]),
+        "é"
+    );
+    assert_eq!(
+        type_keys(&[us_international::QUOTE, us_international::QUOTE_A]),
+        "ä"
+    );
+    // Any pair the layout knows, not just the common ones.
+    assert_eq!(type_keys(&[french::CIRCUMFLEX, french::CIRCUMFLEX_W]), "ŵ");
+    assert_eq!(
+        type_keys(&[
+            vietnamese::CIRCUMFLEX,
+            vietnamese::ACUTE,
+            vietnamese::CIRCUMFLEX_ACUTE_E
+        ]),
+        "ế"
+    );
+}
+
+#[test]
+fn types_uncombined_dead_keys_as_the_layout_does() {
+    // A dead key followed by a letter it doesn't accent, a space, or itself.
+    assert_eq!(type_keys(&[german::ACUTE, german::ACUTE_K]), "´k");
+    assert_eq!(
+        type_keys(&[french::CIRCUMFLEX, french::CIRCUMFLEX_SPACE]),
+        "^"
+    );
+    assert_eq!(type_keys(&[german::ACUTE, german::ACUTE_ACUTE]), "´´");
+    assert_eq!(
+        type_keys(&[us_international::APOSTROPHE, us_international::APOSTROPHE_B]),
+        "'b"
+    );
+    // A key without a character leaves the dead key waiting.
+    assert_eq!(type_keys(&[spanish::ACUTE, us::F1, spanish::ACUTE_N]), "ń");
+}
+
+#[test]
+fn dead_key_is_pending_until_next_key() {
+    let mut dead_keys = DeadKeys::new();
+    assert_eq!(
+        dead_keys.feed(KeyInput::decode(german::ACUTE.0, german::ACUTE.1)),
+        KeyInput::None
+    );
+    assert_eq!(dead_keys.pending(), Some('´'));
+    assert_eq!(
+        dead_keys.feed(KeyInput::decode(us::F1.0, us::F1.1)),
+        KeyInput::None
+    );
+    assert_eq!(dead_keys.pending(), Some('´'));
+    assert_eq!(
+        dead_keys.feed(KeyInput::decode(german::ACUTE_E.0, german::ACUTE_E.1)),
+        KeyInput::Chars(['é'].into_iter().collect())
+    );
+    assert_eq!(dead_keys.pending(), None);
+
+    dead_keys.feed(KeyInput::decode(german::ACUTE.0, german::ACUTE.1));
+    dead_keys.clear();
+    assert_eq!(
+        dead_keys.feed(KeyInput::decode(E.0, E.1)),
+        KeyInput::Chars(['e'].into_iter().collect())
+    );
+}
+
+#[test]
+fn key_chars_are_bounded() {
+    let mut chars = KeyChars::new();
+    assert!("abcd".chars().all(|ch| chars.push(ch)));
+    assert!(!chars.push('e'));
+    assert_eq!(chars.as_slice(), ['a', 'b', 'c', 'd']);
+}
+
+fn avro(input: &str) -> String {
+    let parser = okkhor::parser::Parser::new_phonetic();
+    let mut output = "stale".to_owned();
+    transcribe_ascii(input, &mut output, |input, output| {
+        parser.convert_into(input, output)
+    });
+    output
+}
+
+fn khipro(input: &str) -> String {
+    let scribe = okkhor::khipro::KhiproPhonetic::new();
+    let mut output = "stale".to_owned();
+    transcribe_ascii(input, &mut output, |input, output| {
+        scribe.convert_into(input, output)
+    });
+    output
+}
+
+#[test]
+fn transcribes_around_layout_characters() {
+    for transcribe in [avro, khipro] {
+        assert_eq!(transcribe("é"), "é");
+        assert_eq!(transcribe("ßami"), format!("ß{}", transcribe("ami")));
+        assert_eq!(
+            transcribe("ami éa tumi"),
+            format!("{}é{}", transcribe("ami "), transcribe("a tumi"))
+        );
+        assert_eq!(transcribe("𝔸"), "𝔸");
+    }
+    assert_eq!(
+        avro(&type_keys(&[K, german::ACUTE, german::ACUTE_A, B])),
+        format!("{}á{}", avro("k"), avro("b"))
+    );
+}
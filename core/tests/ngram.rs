@@ -0,0 +1,171 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::ngram::{BuildOptions, ModelError, NgramBuilder, NgramModel, WordHistory};
+
+const CORPUS: &str = "\
+আমি আছি। আমি আছি। আমি আছি।
+আমি করি। আমি করি।
+আমি জানি। আমি জানি। আমি জানি।
+তুমি আমি জানি। তুমি আমি জানি।
+তুমি আমি করি। তুমি আমি করি। তুমি আমি করি।
+আমি যাই।
+";
+
+fn model(options: BuildOptions) -> NgramModel {
+    let mut builder = NgramBuilder::new();
+    builder.add_text(CORPUS);
+    builder.build(options)
+}
+
+fn trained() -> NgramModel {
+    model(BuildOptions::default())
+}
+
+#[test]
+fn ranks_bigram_followers() {
+    let model = trained();
+    assert_eq!(
+        model.predict(&["আমি"], 5),
+        ["করি", "জানি", "আছি"],
+        "by count, ties broken by word"
+    );
+    assert_eq!(model.predict(&["আমি"], 2), ["করি", "জানি"]);
+    assert_eq!(model.predict(&["আমি"], 0), Vec::<&str>::new());
+}
+
+#[test]
+fn prefers_trigrams() {
+    let model = trained();
+    assert_eq!(model.predict(&["তুমি", "আমি"], 5), ["করি", "জানি", "আছি"]);
+    assert_eq!(model.predict(&["তুমি", "আমি"], 1), ["করি"]);
+
+    // An unknown older word falls back to bigrams.
+    assert_eq!(model.predict(&["সে", "আমি"], 5), ["করি", "জানি", "আছি"]);
+    // Only the last two words count.
+    assert_eq!(
+        model.predict(&["সে", "তুমি", "আমি"], 5),
+        model.predict(&["তুমি", "আমি"], 5)
+    );
+}
+
+#[test]
+fn unknown_context_predicts_nothing() {
+    let model = trained();
+    assert!(model.predict(&[], 5).is_empty());
+    assert!(model.predict(&["সে"], 5).is_empty());
+    assert!(model.predict(&["আছি"], 5).is_empty());
+    assert!(NgramModel::default().predict(&["আমি"], 5).is_empty());
+}
+
+#[test]
+fn prunes_rare_ngrams() {
+    let all = model(BuildOptions {
+        min_count: 1,
+        max_followers: 8,
+    });
+    assert_eq!(all.predict(&["আমি"], 5), ["করি", "জানি", "আছি", "যাই"]);
+
+    let frequent = model(BuildOptions {
+        min_count: 4,
+        max_followers: 8,
+    });
+    assert_eq!(frequent.predict(&["আমি"], 5), ["করি", "জানি"]);
+
+    let top = model(BuildOptions {
+        min_count: 1,
+        max_followers: 1,
+    });
+    assert_eq!(top.predict(&["আমি"], 5), ["করি"]);
+}
+
+#[test]
+fn context_breaks_on_punctuation_and_other_text() {
+    let mut builder = NgramBuilder::new();
+    builder.add_text("আমি, আছি। আমি 2 আছি। আমি abc আছি।");
+    let model = builder.build(BuildOptions {
+        min_count: 1,
+        max_followers: 8,
+    });
+    assert!(model.predict(&["আমি"], 5).is_empty());
+}
+
+#[test]
+fn round_trips_through_bytes() {
+    let model = trained();
+    let bytes = model.to_bytes();
+    assert!(bytes.starts_with(b"UONG\x01"));
+    assert_eq!(NgramModel::from_bytes(&bytes), Ok(model.clone()));
+    assert_eq!(
+        NgramModel::from_bytes(&bytes).unwrap().to_bytes(),
+        bytes,
+        "the output is deterministic"
+    );
+
+    let empty = NgramModel::default();
+    assert_eq!(NgramModel::from_bytes(&empty.to_bytes()), Ok(empty));
+}
+
+#[test]
+fn rejects_malformed_models() {
+    let bytes = trained().to_bytes();
+
+    assert_eq!(NgramModel::from_bytes(b""), Err(ModelError::Truncated));
+    assert_eq!(
+        NgramModel::from_bytes(b"NOPE\x01\0\0\0"),
+        Err(ModelError::InvalidMagic)
+    );
+    assert_eq!(
+        NgramModel::from_bytes(b"UONG\x02\0\0\0"),
+        Err(ModelError::UnsupportedVersion(2))
+    );
+    for len in 0..bytes.len() {
+        assert!(NgramModel::from_bytes(&bytes[..len]).is_err(), "{len}");
+    }
+
+    // One word of invalid UTF-8.
+    assert_eq!(
+        NgramModel::from_bytes(b"UONG\x01\x01\x01\xff\0\0"),
+        Err(ModelError::InvalidUtf8)
+    );
+    // A bigram of a word missing from the word list.
+    assert_eq!(
+        NgramModel::from_bytes(b"UONG\x01\x01\x01a\x01\x00\x01\x05\0"),
+        Err(ModelError::InvalidWordId(5))
+    );
+    // A varint running past 32 bits.
+    assert_eq!(
+        NgramModel::from_bytes(b"UONG\x01\xff\xff\xff\xff\xff\x01"),
+        Err(ModelError::Truncated)
+    );
+}
+
+#[test]
+fn word_history_keeps_the_last_two_words() {
+    let mut history = WordHistory::default();
+    assert!(history.is_empty());
+
+    history.push_text("আমি");
+    assert_eq!(history.words(), ["আমি"]);
+    history.push_text(" তুমি সে ");
+    assert_eq!(history.words(), ["তুমি", "সে"]);
+    history.push_text("আমরা");
+    assert_eq!(history.words(), ["সে", "আমরা"]);
+
+    history.clear();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn word_history_resets_on_punctuation() {
+    let mut history = WordHistory::default();
+    history.push_text("আমি তুমি।");
+    assert!(history.is_empty());
+    history.push_text("আমি, তুমি");
+    assert_eq!(history.words(), ["তুমি"]);
+    history.push_text("hello");
+    assert!(history.is_empty());
+
+    history.push_text("তুমি আমি");
+    assert_eq!(trained().predict(&history.words(), 1), ["করি"]);
+}
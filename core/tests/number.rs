@@ -0,0 +1,202 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::collections::HashSet;
+
+use uo_keyboard_core::{
+    number::{self, Number, NumberForm, bangla_digits, group, ordinal, words},
+    text::normalize,
+};
+
+#[test]
+fn small_numbers() {
+    assert_eq!(words(0), "শূন্য");
+    assert_eq!(words(1), "এক");
+    assert_eq!(words(9), "নয়");
+    assert_eq!(words(10), "দশ");
+    assert_eq!(words(11), "এগারো");
+    assert_eq!(words(19), "উনিশ");
+    assert_eq!(words(29), "ঊনত্রিশ");
+    assert_eq!(words(45), "পঁয়তাল্লিশ");
+    assert_eq!(words(71), "একাত্তর");
+    assert_eq!(words(88), "অষ্টাশি");
+    assert_eq!(words(99), "নিরানব্বই");
+}
+
+#[test]
+fn words_below_hundred_are_distinct_single_words() {
+    let words = (0..100).map(words).collect::<Vec<_>>();
+    assert_eq!(words.iter().collect::<HashSet<_>>().len(), 100);
+    for word in &words {
+        assert!(!word.contains(' '), "{word}");
+        assert_eq!(normalize(word), word.as_str());
+    }
+}
+
+#[test]
+fn hundreds() {
+    assert_eq!(words(100), "একশো");
+    assert_eq!(words(101), "একশো এক");
+    assert_eq!(words(345), "তিনশো পঁয়তাল্লিশ");
+    assert_eq!(words(999), "নয়শো নিরানব্বই");
+}
+
+#[test]
+fn thousands_lakhs_and_crores() {
+    assert_eq!(words(1_000), "এক হাজার");
+    assert_eq!(words(12_345), "বারো হাজার তিনশো পঁয়তাল্লিশ");
+    assert_eq!(words(100_000), "এক লাখ");
+    assert_eq!(words(1_234_567), "বারো লাখ চৌত্রিশ হাজার পাঁচশো সাতষট্টি");
+    assert_eq!(words(10_000_000), "এক কোটি");
+    assert_eq!(words(10_000_001), "এক কোটি এক");
+    assert_eq!(
+        words(999_999_999),
+        "নিরানব্বই কোটি নিরানব্বই লাখ নিরানব্বই হাজার নয়শো নিরানব্বই"
+    );
+}
+
+#[test]
+fn crores_of_crores() {
+    assert_eq!(words(1_000_000_000), "একশো কোটি");
+    assert_eq!(words(12_345 * 10_000_000), "বারো হাজার তিনশো পঁয়তাল্লিশ কোটি");
+    assert_eq!(words(100_000_000_000_000), "এক কোটি কোটি");
+    assert!(words(u64::MAX).starts_with("এক লাখ চুরাশি হাজার চারশো সাতষট্টি কোটি"));
+}
+
+#[test]
+fn converts_digits() {
+    assert_eq!(bangla_digits("0123456789"), "০১২৩৪৫৬৭৮৯");
+    assert_eq!(bangla_digits("12.5%"), "১২.৫%");
+    assert_eq!(bangla_digits("১২"), "১২");
+}
+
+#[test]
+fn groups_by_lakh_and_crore() {
+    assert_eq!(group(0), "০");
+    assert_eq!(group(999), "৯৯৯");
+    assert_eq!(group(1_000), "১,০০০");
+    assert_eq!(group(12_345), "১২,৩৪৫");
+    assert_eq!(group(123_456), "১,২৩,৪৫৬");
+    assert_eq!(group(1_234_567), "১২,৩৪,৫৬৭");
+    assert_eq!(group(12_345_678), "১,২৩,৪৫,৬৭৮");
+    assert_eq!(group(1_234_567_890), "১,২৩,৪৫,৬৭,৮৯০");
+}
+
+#[test]
+fn ordinals() {
+    let expected = [
+        "১ম",
+        "২য়",
+        "৩য়",
+        "৪র্থ",
+        "৫ম",
+        "৬ষ্ঠ",
+        "৭ম",
+        "৮ম",
+        "৯ম",
+        "১০ম",
+    ];
+    for (n, expected) in (1..).zip(expected) {
+        assert_eq!(ordinal(n), expected);
+    }
+    assert_eq!(ordinal(11), "১১তম");
+    assert_eq!(ordinal(21), "২১তম");
+    assert_eq!(ordinal(101), "১০১তম");
+}
+
+#[test]
+fn parses_numbers() {
+    assert_eq!(
+        Number::parse("12345"),
+        Some(Number {
+            whole: 12345,
+            fraction: String::new()
+        })
+    );
+    assert_eq!(
+        Number::parse("১২.০৫"),
+        Some(Number {
+            whole: 12,
+            fraction: "05".to_owned()
+        })
+    );
+    assert_eq!(Number::parse(""), None);
+    assert_eq!(Number::parse("12."), None);
+    assert_eq!(Number::parse(".5"), None);
+    assert_eq!(Number::parse("1.2.3"), None);
+    assert_eq!(Number::parse("12a"), None);
+    assert_eq!(Number::parse("-12"), None);
+    assert_eq!(Number::parse("99999999999999999999"), None);
+}
+
+#[test]
+fn fractions() {
+    let number = Number::parse("12.05").unwrap();
+    assert_eq!(number.words(), "বারো দশমিক শূন্য পাঁচ");
+    assert_eq!(number.digits(), "১২.০৫");
+    assert_eq!(number.ordinal(), None);
+}
+
+#[test]
+fn taka() {
+    let taka = |text: &str| Number::parse(text).unwrap().taka();
+    assert_eq!(taka("12345").as_deref(), Some("৳১২,৩৪৫.০০"));
+    assert_eq!(taka("12345.5").as_deref(), Some("৳১২,৩৪৫.৫০"));
+    assert_eq!(taka("0.05").as_deref(), Some("৳০.০৫"));
+    assert_eq!(taka("1.005"), None);
+}
+
+#[test]
+fn taka_in_words() {
+    let taka_words = |text: &str| Number::parse(text).unwrap().taka_words();
+    assert_eq!(
+        taka_words("12345.50").as_deref(),
+        Some("বারো হাজার তিনশো পঁয়তাল্লিশ টাকা পঞ্চাশ পয়সা")
+    );
+    assert_eq!(taka_words("100").as_deref(), Some("একশো টাকা"));
+    assert_eq!(taka_words("0").as_deref(), Some("শূন্য টাকা"));
+    assert_eq!(taka_words("0.25").as_deref(), Some("পঁচিশ পয়সা"));
+    assert_eq!(taka_words("1.005"), None);
+}
+
+#[test]
+fn form_suffixes() {
+    for form in NumberForm::ALL {
+        assert_eq!(NumberForm::from_suffix(form.suffix()), Some(form));
+    }
+    assert_eq!(NumberForm::from_suffix("T"), Some(NumberForm::Taka));
+    assert_eq!(NumberForm::from_suffix("x"), None);
+}
+
+#[test]
+fn converts_shortcodes() {
+    assert_eq!(
+        number::convert("১২৩৪৫").as_deref(),
+        Some("বারো হাজার তিনশো পঁয়তাল্লিশ")
+    );
+    assert_eq!(number::convert("1234567d").as_deref(), Some("১২,৩৪,৫৬৭"));
+    assert_eq!(number::convert("2o").as_deref(), Some("২য়"));
+    assert_eq!(number::convert("12345.5t").as_deref(), Some("৳১২,৩৪৫.৫০"));
+    assert_eq!(number::convert("1000tw").as_deref(), Some("এক হাজার টাকা"));
+    assert_eq!(number::convert("2.5o"), None);
+    assert_eq!(number::convert("12x"), None);
+    assert_eq!(number::convert("smile"), None);
+    assert_eq!(number::convert(""), None);
+}
+
+#[test]
+fn lists_forms() {
+    let forms = number::forms("4");
+    assert_eq!(
+        forms,
+        [
+            (NumberForm::Words, "চার".to_owned()),
+            (NumberForm::Digits, "৪".to_owned()),
+            (NumberForm::Ordinal, "৪র্থ".to_owned()),
+            (NumberForm::Taka, "৳৪.০০".to_owned()),
+            (NumberForm::TakaWords, "চার টাকা".to_owned()),
+        ]
+    );
+    assert_eq!(number::forms("1.5").len(), 4);
+    assert!(number::forms("rofl").is_empty());
+}
@@ -0,0 +1,257 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{cell::RefCell, rc::Rc};
+
+use uo_keyboard_core::ops::{
+    EditOp, LockMode, LockResponse, OpQueue, apply_queued, request_session,
+};
+
+/// A document granting locks as told, recording the operations applied by edit sessions.
+#[derive(Debug, Default)]
+struct MockDocument {
+    queue: Rc<RefCell<OpQueue>>,
+    applied: Vec<EditOp>,
+    /// Whether a synchronous lock is refused, e.g. while the application is busy.
+    refuses_sync: bool,
+    /// An operation failing to apply the next time it's seen.
+    failing: Option<EditOp>,
+    /// Asynchronous edit sessions waiting for the lock.
+    scheduled: usize,
+    sync_requests: usize,
+    async_requests: usize,
+}
+
+impl MockDocument {
+    fn request(&mut self, mode: LockMode) -> Result<LockResponse, ()> {
+        match mode {
+            LockMode::Sync if self.refuses_sync => {
+                self.sync_requests += 1;
+                Ok(LockResponse::SyncRefused)
+            }
+            LockMode::Sync => {
+                self.sync_requests += 1;
+                self.run_edit_session();
+                Ok(LockResponse::Done)
+            }
+            LockMode::Async => {
+                self.async_requests += 1;
+                self.scheduled += 1;
+                Ok(LockResponse::Scheduled)
+            }
+        }
+    }
+
+    /// Stands for a key event queueing `ops` and asking for an edit session.
+    fn key_event(&mut self, ops: impl IntoIterator<Item = EditOp>) -> LockResponse {
+        self.queue.borrow_mut().extend(ops);
+        request_session(|mode| self.request(mode)).unwrap()
+    }
+
+    /// Grants the lock to the scheduled edit sessions.
+    fn release(&mut self) {
+        for _ in 0..std::mem::take(&mut self.scheduled) {
+            self.run_edit_session();
+        }
+    }
+
+    fn run_edit_session(&mut self) {
+        // Like the IME, the queue isn't borrowed while an operation is applied.
+        let queue = self.queue.clone();
+        let result = apply_queued(
+            || queue.borrow_mut().pop(),
+            |op| {
+                if self.failing.take_if(|failing| *failing == op).is_some() {
+                    return Err(op);
+                }
+                self.applied.push(op);
+                Ok(())
+            },
+        );
+
+        // Like the IME, the operations after a failing one are left to another edit session.
+        if result.is_err() && !self.queue.borrow().is_empty() {
+            self.async_requests += 1;
+            self.scheduled += 1;
+        }
+    }
+}
+
+fn typed(text: &str) -> Vec<EditOp> {
+    text.chars().map(EditOp::Append).collect()
+}
+
+#[test]
+fn sync_lock_applies_right_away() {
+    let mut doc = MockDocument::default();
+
+    assert_eq!(doc.key_event(typed("a")), LockResponse::Done);
+    assert_eq!(doc.key_event([EditOp::Finish]), LockResponse::Done);
+
+    assert_eq!(doc.applied, [EditOp::Append('a'), EditOp::Finish]);
+    assert_eq!((doc.sync_requests, doc.async_requests), (2, 0));
+}
+
+#[test]
+fn refused_sync_lock_falls_back_to_async() {
+    let mut doc = MockDocument {
+        refuses_sync: true,
+        ..Default::default()
+    };
+
+    assert_eq!(doc.key_event(typed("ami")), LockResponse::Scheduled);
+    assert!(doc.applied.is_empty());
+    assert_eq!(doc.queue.borrow().len(), 3);
+
+    doc.release();
+    assert_eq!(doc.applied, typed("ami"));
+    assert!(doc.queue.borrow().is_empty());
+}
+
+#[test]
+fn no_input_is_dropped_or_reordered() {
+    let mut doc = MockDocument::default();
+    let mut expected = Vec::new();
+
+    // The document refuses synchronous locks now and then, and grants the pending
+    // asynchronous ones at other times.
+    let keys = typed("ami banglay gan gai").into_iter().chain([
+        EditOp::Backspace,
+        EditOp::Append('i'),
+        EditOp::Finish,
+        EditOp::Insert('1'),
+        EditOp::Cancel,
+    ]);
+    for (i, op) in keys.enumerate() {
+        doc.refuses_sync = i % 3 != 0;
+        if i % 5 == 4 {
+            doc.release();
+        }
+
+        expected.push(op);
+        doc.key_event([op]);
+        assert!(expected.starts_with(&doc.applied));
+    }
+
+    doc.release();
+    assert_eq!(doc.applied, expected);
+}
+
+#[test]
+fn multiple_ops_of_a_key_stay_together() {
+    let mut doc = MockDocument {
+        refuses_sync: true,
+        ..Default::default()
+    };
+
+    doc.key_event(typed("1"));
+    doc.key_event([EditOp::Append('.'), EditOp::Append('`')]);
+    doc.refuses_sync = false;
+    doc.key_event(typed("5"));
+
+    // The synchronous session applies what the earlier ones left behind first.
+    assert_eq!(doc.applied, typed("1.`5"));
+    doc.release();
+    assert_eq!(doc.applied, typed("1.`5"));
+}
+
+#[test]
+fn failing_op_is_dropped_alone() {
+    let mut doc = MockDocument {
+        failing: Some(EditOp::Backspace),
+        ..Default::default()
+    };
+
+    doc.key_event(typed("ami"));
+    doc.key_event([EditOp::Backspace, EditOp::Append('o'), EditOp::Finish]);
+    assert_eq!(doc.applied, typed("ami"));
+    assert_eq!(doc.queue.borrow().len(), 2);
+    assert_eq!(doc.scheduled, 1);
+
+    doc.release();
+    let mut expected = typed("amio");
+    expected.push(EditOp::Finish);
+    assert_eq!(doc.applied, expected);
+    assert!(doc.queue.borrow().is_empty());
+    assert_eq!(doc.scheduled, 0);
+}
+
+#[test]
+fn failing_last_op_needs_no_session() {
+    let mut doc = MockDocument {
+        failing: Some(EditOp::Finish),
+        ..Default::default()
+    };
+
+    doc.key_event(typed("a").into_iter().chain([EditOp::Finish]));
+    assert_eq!(doc.applied, typed("a"));
+    assert!(doc.queue.borrow().is_empty());
+    assert_eq!(doc.async_requests, 0);
+}
+
+/// Stands for a key ending the composition, which the application gets right away only if
+/// the commit is done by then.
+fn end_key(doc: &mut MockDocument, key: u16) -> bool {
+    doc.key_event([EditOp::Finish]);
+    !doc.queue.borrow_mut().push_behind(EditOp::Forward(key))
+}
+
+#[test]
+fn ending_key_is_forwarded_after_a_sync_commit() {
+    let mut doc = MockDocument::default();
+    doc.key_event(typed("ami"));
+    assert!(end_key(&mut doc, 0x20));
+
+    let mut expected = typed("ami");
+    expected.push(EditOp::Finish);
+    assert_eq!(doc.applied, expected);
+    assert!(doc.queue.borrow().is_empty());
+}
+
+#[test]
+fn ending_key_waits_for_an_async_commit() {
+    let mut doc = MockDocument {
+        refuses_sync: true,
+        ..Default::default()
+    };
+    doc.key_event(typed("ami"));
+    assert!(!end_key(&mut doc, 0x20));
+    doc.key_event(typed("t"));
+
+    doc.release();
+    let mut expected = typed("ami");
+    expected.extend([EditOp::Finish, EditOp::Forward(0x20), EditOp::Append('t')]);
+    assert_eq!(doc.applied, expected);
+}
+
+#[test]
+fn projected_input_len() {
+    let mut queue = OpQueue::new();
+    assert_eq!(queue.projected_input_len(0), 0);
+    assert_eq!(queue.projected_input_len(3), 3);
+
+    queue.extend(typed("ab"));
+    assert_eq!(queue.projected_input_len(0), 2);
+    queue.push(EditOp::NextCandidate);
+    assert_eq!(queue.projected_input_len(0), 2);
+
+    queue.extend([EditOp::Backspace, EditOp::Insert('1')]);
+    assert_eq!(queue.projected_input_len(0), 1);
+    assert!(queue.push_behind(EditOp::Forward(0x25)));
+    assert_eq!(queue.projected_input_len(0), 1);
+
+    queue.extend([EditOp::Backspace, EditOp::Backspace]);
+    assert_eq!(queue.projected_input_len(0), 0);
+
+    queue.extend(typed("k"));
+    queue.push(EditOp::Finish);
+    assert_eq!(queue.projected_input_len(5), 0);
+
+    queue.extend(typed("to"));
+    queue.push(EditOp::Revert);
+    assert_eq!(queue.projected_input_len(0), 0);
+
+    queue.clear();
+    assert!(!queue.push_behind(EditOp::Forward(0x25)));
+    assert!(queue.is_empty());
+}
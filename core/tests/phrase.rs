@@ -0,0 +1,217 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::cell::Cell;
+
+use okkhor::{khipro::KhiproPhonetic, parser::Parser};
+use uo_keyboard_core::{
+    ngram::{BuildOptions, NgramBuilder},
+    phrase::{CANDIDATE_LIMIT, CompositionMode, PhraseCandidates, ends_phrase},
+    preedit::Preedit,
+    spell::SpellChecker,
+};
+
+#[test]
+fn mode_names() {
+    for mode in [CompositionMode::Word, CompositionMode::Phrase] {
+        assert_eq!(CompositionMode::from_name(mode.name()), Some(mode));
+    }
+    assert_eq!(CompositionMode::from_name("sentence"), None);
+    assert_eq!(CompositionMode::default(), CompositionMode::Word);
+}
+
+#[test]
+fn punctuation_ends_phrases() {
+    assert!(ends_phrase("ami bhalo achi."));
+    assert!(ends_phrase("tumi kemon acho?"));
+    assert!(ends_phrase("ami,"));
+    assert!(!ends_phrase("ami"));
+    assert!(!ends_phrase(""));
+
+    assert!(CompositionMode::Phrase.keeps_space("ami"));
+    assert!(!CompositionMode::Phrase.keeps_space("ami!"));
+    assert!(!CompositionMode::Word.keeps_space("ami"));
+}
+
+/// Types `phrase` a key at a time, counting the conversions made for each key.
+fn type_phrase(phrase: &str, convert: impl Fn(&str, &mut String)) -> (Preedit, Vec<usize>) {
+    let calls = Cell::new(0);
+    let counted = |input: &str, output: &mut String| {
+        calls.set(calls.get() + 1);
+        convert(input, output);
+    };
+
+    let mut preedit = Preedit::new();
+    let mut per_key = Vec::new();
+    for (end, _) in phrase.char_indices().skip(1).chain([(phrase.len(), ' ')]) {
+        calls.set(0);
+        preedit.convert(&phrase[..end], counted);
+        per_key.push(calls.get());
+    }
+    (preedit, per_key)
+}
+
+fn check_phrase(phrase: &str, convert: impl Fn(&str, &mut String)) {
+    let (preedit, per_key) = type_phrase(phrase, &convert);
+
+    // Only the word being typed is converted again.
+    assert!(per_key.iter().all(|&calls| calls == 1), "{per_key:?}");
+
+    let words = preedit.words().collect::<Vec<_>>();
+    assert_eq!(
+        words.iter().map(|(input, _)| *input).collect::<String>(),
+        phrase
+    );
+    for (input, output) in &words {
+        let mut expected = String::new();
+        convert(input, &mut expected);
+        assert_eq!(*output, expected);
+    }
+    assert_eq!(
+        words.iter().map(|(_, output)| *output).collect::<String>(),
+        preedit.text()
+    );
+}
+
+#[test]
+fn avro_phrases_convert_a_word_at_a_time() {
+    let parser = Parser::new_phonetic();
+    check_phrase("ami banglay gan gai.", |input, output| {
+        parser.convert_into(input, output)
+    });
+}
+
+#[test]
+fn khipro_phrases_convert_a_word_at_a_time() {
+    let scribe = KhiproPhonetic::new();
+    check_phrase("ami banglay gan gai.", |input, output| {
+        scribe.convert_into(input, output)
+    });
+}
+
+#[test]
+fn text_set_as_is_has_no_words() {
+    let parser = Parser::new_phonetic();
+    let (mut preedit, _) = type_phrase("ami tumi", |input, output| {
+        parser.convert_into(input, output)
+    });
+    assert_eq!(preedit.words().count(), 2);
+    preedit.set_text("😀");
+    assert_eq!(preedit.words().count(), 0);
+}
+
+fn alternates_of<'a>(table: &'a [(&str, &[&str])]) -> impl FnMut(&str, &str) -> Vec<String> + 'a {
+    |word, _| {
+        table
+            .iter()
+            .find(|(of, _)| *of == word)
+            .map(|(_, alternates)| alternates.iter().map(|&a| a.to_owned()).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn candidates_swap_a_word_at_a_time() {
+    let words = [("ka ", "ক "), ("kha ", "খ "), ("ga", "গ")];
+    let table: &[(&str, &[&str])] = &[("খ", &["ঘ", "ঙ"]), ("গ", &["চ"])];
+    let mut candidates =
+        PhraseCandidates::new(words, &[], alternates_of(table), |_: &[&str], _: &str| {
+            false
+        });
+
+    assert_eq!(
+        candidates.candidates(),
+        ["ক খ গ", "ক ঘ গ", "ক ঙ গ", "ক খ চ"]
+    );
+    assert_eq!(candidates.selected(), "ক খ গ");
+    assert_eq!(candidates.select_next(), "ক ঘ গ");
+    assert_eq!(candidates.select_next(), "ক ঙ গ");
+    assert_eq!(candidates.select_next(), "ক খ চ");
+    assert_eq!(candidates.select_next(), "ক খ গ");
+}
+
+#[test]
+fn words_around_rank_the_candidates() {
+    let words = [("ka ", "ক "), ("kha ", "খ "), ("ga", "গ")];
+    let table: &[(&str, &[&str])] = &[("খ", &["ঘ", "ঙ"]), ("গ", &["চ"])];
+    let follows = |context: &[&str], word: &str| {
+        matches!(
+            (context.last(), word),
+            (Some(&"ক"), "ঙ") | (Some(&"ঙ"), "গ")
+        )
+    };
+    let candidates = PhraseCandidates::new(words, &[], alternates_of(table), follows);
+
+    // The phrase as converted stays first, even when an alternate fits better.
+    assert_eq!(
+        candidates.candidates(),
+        ["ক খ গ", "ক ঙ গ", "ক ঘ গ", "ক খ চ"]
+    );
+}
+
+#[test]
+fn committed_words_rank_the_candidates() {
+    let table: &[(&str, &[&str])] = &[("কাল", &["কল", "খাল"])];
+    let follows = |context: &[&str], word: &str| context.last() == Some(&"নদীর") && word == "খাল";
+
+    let candidates = PhraseCandidates::new([("kal", "কাল")], &[], alternates_of(table), follows);
+    assert_eq!(candidates.candidates(), ["কাল", "কল", "খাল"]);
+
+    let candidates =
+        PhraseCandidates::new([("kal", "কাল")], &["নদীর"], alternates_of(table), follows);
+    assert_eq!(candidates.candidates(), ["কাল", "খাল", "কল"]);
+
+    // Punctuation in between breaks the context.
+    let candidates = PhraseCandidates::new(
+        [("|", "। "), ("kal", "কাল")],
+        &["নদীর"],
+        alternates_of(table),
+        follows,
+    );
+    assert_eq!(candidates.candidates(), ["। কাল", "। কল", "। খাল"]);
+}
+
+#[test]
+fn candidates_are_limited() {
+    let words = [("ka ", "ক "), ("kha ", "খ "), ("ga", "গ")];
+    let many: &[&str] = &["ঘ", "ঙ", "চ", "ছ", "জ", "ঝ"];
+    let table: &[(&str, &[&str])] = &[("ক", many), ("খ", many), ("গ", many)];
+    let candidates =
+        PhraseCandidates::new(words, &[], alternates_of(table), |_: &[&str], _: &str| {
+            false
+        });
+
+    assert_eq!(candidates.candidates().len(), CANDIDATE_LIMIT);
+    // Each word tries only its closest few alternates.
+    assert!(!candidates.candidates().iter().any(|c| c.contains('জ')));
+}
+
+#[test]
+fn avro_phrase_candidates_follow_the_language_model() {
+    let parser = Parser::new_phonetic();
+    let (preedit, _) = type_phrase("nodir kal", |input, output| {
+        parser.convert_into(input, output)
+    });
+    let converted = preedit.text().to_owned();
+
+    let mut checker = SpellChecker::default();
+    checker.extend_from_str("কাল\nখাল\nনদির\n");
+    let mut builder = NgramBuilder::new();
+    builder.add_text("নদির খাল শুকিয়ে গেছে। নদির খাল বেয়ে নৌকা চলে।");
+    let model = builder.build(BuildOptions::default());
+
+    let candidates = PhraseCandidates::new(
+        preedit.words(),
+        &[],
+        |word, roman| {
+            checker
+                .alternates(word, Some(roman), 10)
+                .into_iter()
+                .map(|alternate| alternate.word)
+                .collect()
+        },
+        |context, word| model.predict(context, 8).contains(&word),
+    );
+    assert_eq!(candidates.candidates()[0], converted);
+    assert_eq!(candidates.candidates()[1], "নদির খাল");
+}
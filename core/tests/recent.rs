@@ -0,0 +1,187 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use okkhor::{khipro::KhiproPhonetic, parser::Parser};
+use uo_keyboard_core::{
+    preedit::Preedit,
+    recent::{CAPACITY, Commit, RecentCommits, Reverted},
+};
+
+fn commits(words: &[(&str, &str)]) -> RecentCommits {
+    let mut recent = RecentCommits::new();
+    for (roman, text) in words {
+        recent.push(roman, text);
+    }
+    recent
+}
+
+#[test]
+fn remembers_commits_in_order() {
+    let mut recent = commits(&[("ami", "আমি"), ("tumi", "তুমি")]);
+    assert_eq!(recent.len(), 2);
+    assert_eq!(
+        recent.pop(),
+        Some(Commit {
+            roman: "tumi".to_owned(),
+            text: "তুমি".to_owned()
+        })
+    );
+    assert_eq!(recent.last().map(|c| c.roman.as_str()), Some("ami"));
+}
+
+#[test]
+fn skips_empty_commits() {
+    let recent = commits(&[("", ""), ("o", ""), ("", "a")]);
+    assert!(recent.is_empty());
+}
+
+#[test]
+fn forgets_the_oldest() {
+    let mut recent = RecentCommits::new();
+    for i in 0..CAPACITY + 2 {
+        recent.push(&i.to_string(), &i.to_string());
+    }
+    assert_eq!(recent.len(), CAPACITY);
+    while recent.len() > 1 {
+        recent.pop();
+    }
+    assert_eq!(recent.last().map(|c| c.roman.as_str()), Some("2"));
+}
+
+#[test]
+fn reads_just_past_the_commit() {
+    let recent = commits(&[("ami", "আমি")]);
+    assert_eq!(recent.context_len(), Some(4));
+    assert_eq!(RecentCommits::new().context_len(), None);
+}
+
+#[test]
+fn finds_the_commit_before_the_caret() {
+    let recent = commits(&[("ami", "আমি")]);
+    assert_eq!(recent.reopenable_len("আমি"), Some(3));
+    assert_eq!(recent.reopenable_len("আমি "), Some(4));
+    assert_eq!(recent.reopenable_len("আমি\t"), Some(4));
+    assert_eq!(recent.reopenable_len(" আমি "), Some(4));
+}
+
+#[test]
+fn ignores_changed_text() {
+    let recent = commits(&[("ami", "আমি")]);
+    assert_eq!(recent.reopenable_len("আমি  "), None);
+    assert_eq!(recent.reopenable_len("আমি\r\n"), None);
+    assert_eq!(recent.reopenable_len("আমিx"), None);
+    assert_eq!(recent.reopenable_len("আম"), None);
+    assert_eq!(recent.reopenable_len(""), None);
+    assert_eq!(RecentCommits::new().reopenable_len("আমি"), None);
+}
+
+#[test]
+fn keeps_commits_past_a_forwarded_terminator() {
+    // The space committing আমি is forwarded, and the application types it after the commit.
+    let mut recent = commits(&[("ami", "আমি")]);
+    recent.caret_moved("আমি ");
+    assert_eq!(recent.reopenable_len("আমি "), Some(4));
+    assert_eq!(recent.pop().map(|c| c.roman), Some("ami".to_owned()));
+
+    let mut recent = commits(&[("ami", "আমি")]);
+    recent.caret_moved("আমি");
+    assert_eq!(recent.len(), 1);
+}
+
+#[test]
+fn forgets_commits_once_the_caret_leaves_them() {
+    let mut recent = commits(&[("ami", "আমি"), ("tumi", "তুমি")]);
+    recent.caret_moved("তুমি x");
+    assert!(recent.is_empty());
+
+    let mut recent = commits(&[("ami", "আমি")]);
+    recent.caret_moved("");
+    assert!(recent.is_empty());
+}
+
+#[test]
+fn counts_utf16_units() {
+    let recent = commits(&[(":rofl:", "🤣")]);
+    assert_eq!(recent.context_len(), Some(3));
+    assert_eq!(recent.reopenable_len("🤣 "), Some(3));
+}
+
+#[test]
+fn reverts_the_commit_before_the_caret() {
+    let mut recent = commits(&[("ami", "আমি"), ("Tumi", "তুমি")]);
+    assert_eq!(
+        recent.revert("আমি তুমি "),
+        Some(Reverted {
+            len: 5,
+            text: "Tumi ".to_owned()
+        })
+    );
+    assert_eq!(
+        recent.revert("আমি"),
+        Some(Reverted {
+            len: 3,
+            text: "ami".to_owned()
+        })
+    );
+    assert!(recent.is_empty());
+}
+
+#[test]
+fn keeps_commits_not_before_the_caret() {
+    let mut recent = commits(&[("ami", "আমি")]);
+    assert_eq!(recent.revert("আমি  "), None);
+    assert_eq!(recent.len(), 1);
+}
+
+/// Composes `typed` key by key as the IME does, where `adapt` stands for the changes the
+/// transcriber makes to typed keys, then commits it and reverts the commit.
+fn compose_and_revert(
+    typed: &str,
+    adapt: impl Fn(char) -> char,
+    mut convert: impl FnMut(&str, &mut String),
+) -> (String, String, Option<Reverted>) {
+    let mut preedit = Preedit::new();
+    let mut input = String::new();
+    for ch in typed.chars() {
+        input.push(adapt(ch));
+        preedit.convert(&input, &mut convert);
+    }
+    let converted = preedit.text().to_owned();
+
+    // Reverting a live composition shows what was typed.
+    preedit.set_text(typed);
+    assert_eq!(preedit.text(), typed);
+
+    let mut recent = RecentCommits::new();
+    recent.push(typed, &converted);
+    let reverted = recent.revert(&format!("{converted} "));
+    (input, converted, reverted)
+}
+
+#[test]
+fn reverts_avro_conversions() {
+    let parser = Parser::new_phonetic();
+    let (_, converted, reverted) =
+        compose_and_revert("Hello", |ch| ch, |i, o| parser.convert_into(i, o));
+    assert_ne!(converted, "Hello");
+    assert_eq!(
+        reverted,
+        Some(Reverted {
+            len: converted.encode_utf16().count() + 1,
+            text: "Hello ".to_owned()
+        })
+    );
+}
+
+#[test]
+fn reverts_khipro_conversions_as_typed() {
+    let scribe = KhiproPhonetic::new();
+    let (input, converted, reverted) = compose_and_revert(
+        "Hello",
+        |ch| ch.to_ascii_lowercase(),
+        |i, o| scribe.convert_into(i, o),
+    );
+    assert_eq!(input, "hello");
+    assert_ne!(converted, "hello");
+    assert_eq!(reverted.map(|r| r.text), Some("Hello ".to_owned()));
+}
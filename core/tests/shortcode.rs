@@ -0,0 +1,116 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::{
+    calendar::{Date, DateFormat, Time},
+    shortcode::{Shortcode, ShortcodeTable},
+};
+
+fn now() -> (Date, Time) {
+    (
+        Date::new(2026, 10, 19).unwrap(),
+        Time { hour: 9, minute: 5 },
+    )
+}
+
+fn never() -> (Date, Time) {
+    panic!("the time is only read for the date shortcode")
+}
+
+fn symbol(text: &str) -> Shortcode<'_> {
+    Shortcode::Symbol(text.into())
+}
+
+#[test]
+fn resolves_codes() {
+    let table = ShortcodeTable::default();
+    assert_eq!(table.resolve(":", ":taka:", never), symbol("৳"));
+    assert_eq!(table.resolve(":", ":taka", never), symbol("৳"));
+    assert_eq!(table.resolve(":", ":SMILE:", never), symbol("😄"));
+    assert_eq!(table.resolve(":", ":+1:", never), symbol("👍"));
+    assert_eq!(table.resolve(":", ":", never), Shortcode::Partial);
+    assert_eq!(table.resolve(":", ":smi", never), Shortcode::Partial);
+    assert_eq!(table.resolve(":", ":nothing:", never), Shortcode::Partial);
+    assert_eq!(table.resolve(":", "taka", never), Shortcode::None);
+    assert_eq!(table.resolve(":", "", never), Shortcode::None);
+}
+
+#[test]
+fn resolves_longer_prefixes() {
+    let table = ShortcodeTable::default();
+    assert_eq!(table.resolve(";;", ";;taka;;", never), symbol("৳"));
+    assert_eq!(table.resolve(";;", ";;taka", never), symbol("৳"));
+    assert_eq!(table.resolve(";;", ";taka", never), Shortcode::None);
+    assert_eq!(table.resolve(";;", ":taka:", never), Shortcode::None);
+}
+
+#[test]
+fn empty_prefix_disables_shortcodes() {
+    let table = ShortcodeTable::default();
+    assert_eq!(table.resolve("", "taka", never), Shortcode::None);
+    assert_eq!(table.resolve("", ":taka:", never), Shortcode::None);
+    assert_eq!(table.resolve("", "", never), Shortcode::None);
+}
+
+#[test]
+fn user_codes_add_and_override() {
+    let mut table = ShortcodeTable::default();
+    let malformed = table.extend_from_str(
+        "\
+; comment
+# another comment
+
+Taka = টাকা
+namaste = 🙏
+  shrug  =  🤷
+",
+    );
+    assert!(malformed.is_empty());
+    assert_eq!(table.get("taka"), Some("টাকা"));
+    assert_eq!(table.get("NAMASTE"), Some("🙏"));
+    assert_eq!(table.get("shrug"), Some("🤷"));
+    assert_eq!(table.get("smile"), Some("😄"));
+    assert_eq!(table.resolve(":", ":taka:", never), symbol("টাকা"));
+}
+
+#[test]
+fn reports_malformed_lines() {
+    let mut table = ShortcodeTable::default();
+    let malformed = table.extend_from_str("heart\n = 💔\nheart =\nok = 🆗\n\n=\n");
+    assert_eq!(malformed, [1, 2, 3, 6]);
+    assert_eq!(table.get("heart"), Some("❤️"));
+    assert_eq!(table.get("ok"), Some("🆗"));
+}
+
+#[test]
+fn numbers_need_a_closing_prefix() {
+    let table = ShortcodeTable::default();
+    assert_eq!(table.resolve(":", ":30:", never), symbol("ত্রিশ"));
+    assert_eq!(table.resolve(":", ":30", never), Shortcode::Partial);
+    assert_eq!(table.resolve(":", ":12345t:", never), symbol("৳১২,৩৪৫.০০"));
+    assert_eq!(table.resolve(":", ":12345t", never), Shortcode::Partial);
+    assert_eq!(table.resolve(":", "::", never), Shortcode::Partial);
+}
+
+#[test]
+fn date_code() {
+    let mut table = ShortcodeTable::default();
+    assert_eq!(table.resolve(":", ":date:", now), Shortcode::Partial);
+
+    table.set_date_formats(vec![
+        DateFormat::parse("bangabda:%d %B %Y"),
+        DateFormat::parse("%d %B %Y"),
+    ]);
+    assert_eq!(table.resolve(":", ":date:", now), symbol("৩ কার্তিক ১৪৩৩"));
+    assert_eq!(table.resolve(":", ":date", never), Shortcode::Partial);
+
+    assert_eq!(table.resolve(":", ":date1:", now), symbol("৩ কার্তিক ১৪৩৩"));
+    assert_eq!(table.resolve(":", ":DATE2:", now), symbol("১৯ অক্টোবর ২০২৬"));
+    assert_eq!(table.resolve(":", ":date3:", never), Shortcode::Partial);
+    assert_eq!(table.resolve(":", ":date0:", never), Shortcode::Partial);
+    assert_eq!(table.resolve(":", ":dates:", never), Shortcode::Partial);
+
+    table.insert("date", "📅");
+    assert_eq!(table.resolve(":", ":date:", never), symbol("📅"));
+    assert_eq!(table.resolve(":", ":date2:", now), symbol("১৯ অক্টোবর ২০২৬"));
+}
@@ -0,0 +1,154 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! A mock source checking that every advised sink is unadvised exactly once.
+
+use std::{cell::RefCell, rc::Rc};
+
+use uo_keyboard_core::sink::{Advised, SinkGuard, SinkStack};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event {
+    Advise(u32),
+    Unadvise(u32),
+}
+
+#[derive(Debug, Default)]
+struct Source {
+    events: RefCell<Vec<Event>>,
+    next_cookie: RefCell<u32>,
+    /// Cookies whose unadvise fails.
+    failing: RefCell<Vec<u32>>,
+}
+
+impl Source {
+    fn advise(self: &Rc<Self>) -> Sink {
+        let mut next = self.next_cookie.borrow_mut();
+        *next += 1;
+        self.events.borrow_mut().push(Event::Advise(*next));
+        Sink {
+            source: self.clone(),
+            cookie: *next,
+        }
+    }
+
+    fn events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+
+    /// Asserts that every advised cookie was unadvised exactly once, after being advised.
+    fn assert_balanced(&self) {
+        let events = self.events();
+        for (i, event) in events.iter().enumerate() {
+            match *event {
+                Event::Advise(cookie) => assert_eq!(
+                    events[i..]
+                        .iter()
+                        .filter(|&&e| e == Event::Unadvise(cookie))
+                        .count(),
+                    1,
+                    "{cookie} in {events:?}"
+                ),
+                Event::Unadvise(cookie) => {
+                    assert!(events[..i].contains(&Event::Advise(cookie)), "{events:?}")
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Sink {
+    source: Rc<Source>,
+    cookie: u32,
+}
+
+impl Advised for Sink {
+    type Error = u32;
+
+    fn unadvise(&self) -> Result<(), u32> {
+        self.source
+            .events
+            .borrow_mut()
+            .push(Event::Unadvise(self.cookie));
+        if self.source.failing.borrow().contains(&self.cookie) {
+            Err(self.cookie)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Advises `count` sinks like an activation, failing before advising the last one if asked.
+fn activate(source: &Rc<Source>, count: usize, fail: bool) -> Result<SinkStack<Sink>, ()> {
+    let mut sinks = SinkStack::new();
+    for i in 0..count {
+        if fail && i == count - 1 {
+            return Err(());
+        }
+        sinks.push(source.advise());
+    }
+    Ok(sinks)
+}
+
+#[test]
+fn guard_unadvises_on_drop() {
+    let source = Rc::new(Source::default());
+    drop(SinkGuard::new(source.advise()));
+    assert_eq!(source.events(), [Event::Advise(1), Event::Unadvise(1)]);
+}
+
+#[test]
+fn released_guard_unadvises_once() {
+    let source = Rc::new(Source::default());
+    source.failing.borrow_mut().push(1);
+    assert_eq!(SinkGuard::new(source.advise()).release(), Err(1));
+    source.assert_balanced();
+}
+
+#[test]
+fn deactivation_unadvises_in_reverse() {
+    let source = Rc::new(Source::default());
+    let sinks = activate(&source, 6, false).unwrap();
+    assert_eq!(sinks.len(), 6);
+    sinks.release().unwrap();
+
+    let unadvised: Vec<_> = source
+        .events()
+        .into_iter()
+        .filter_map(|e| match e {
+            Event::Unadvise(cookie) => Some(cookie),
+            Event::Advise(_) => None,
+        })
+        .collect();
+    assert_eq!(unadvised, [6, 5, 4, 3, 2, 1]);
+    source.assert_balanced();
+}
+
+#[test]
+fn partial_activation_is_balanced() {
+    for count in 1..=6 {
+        let source = Rc::new(Source::default());
+        assert!(activate(&source, count, true).is_err());
+        assert_eq!(source.events().len(), 2 * (count - 1));
+        source.assert_balanced();
+    }
+}
+
+#[test]
+fn failed_unadvise_doesnt_stop_the_rest() {
+    let source = Rc::new(Source::default());
+    source.failing.borrow_mut().extend([2, 4]);
+    let sinks = activate(&source, 5, false).unwrap();
+    // The first failure met, unadvising from the last sink.
+    assert_eq!(sinks.release(), Err(4));
+    source.assert_balanced();
+}
+
+#[test]
+fn dropped_stack_is_balanced() {
+    let source = Rc::new(Source::default());
+    let sinks = activate(&source, 3, false).unwrap();
+    drop(sinks);
+    source.assert_balanced();
+}
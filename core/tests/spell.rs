@@ -0,0 +1,222 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::{
+    spell::{Misspelling, SpellChecker, Suggestion},
+    text::{graphemes, normalize},
+};
+
+fn word_list(words: &str) -> SpellChecker {
+    let mut checker = SpellChecker::default();
+    checker.extend_from_str(words);
+    checker
+}
+
+fn suggest(checker: &SpellChecker, word: &str, roman: Option<&str>) -> Vec<(String, usize)> {
+    checker
+        .suggest(word, roman, 10)
+        .into_iter()
+        .map(|Suggestion { word, distance }| (word, distance))
+        .collect()
+}
+
+/// Words whose phonetic key is the key of `roman`, probing the keys through alternates.
+fn read_as(checker: &SpellChecker, roman: &str) -> Vec<String> {
+    checker
+        .alternates("", Some(roman), 10)
+        .into_iter()
+        .map(|alternate| alternate.word)
+        .collect()
+}
+
+fn entry(word: &str, distance: usize) -> (String, usize) {
+    (word.to_owned(), distance)
+}
+
+#[test]
+fn splits_graphemes() {
+    assert_eq!(graphemes("কলম").collect::<Vec<_>>(), ["ক", "ল", "ম"]);
+    assert_eq!(graphemes("বাংলা").collect::<Vec<_>>(), ["বাং", "লা"]);
+    assert_eq!(graphemes("রক্ত").collect::<Vec<_>>(), ["র", "ক্ত"]);
+    assert_eq!(graphemes("স্ত্রী").collect::<Vec<_>>(), ["স্ত্রী"]);
+    assert_eq!(graphemes("র‍্যাব").collect::<Vec<_>>(), ["র‍্যা", "ব"]);
+    assert_eq!(graphemes("চাঁদ").collect::<Vec<_>>(), ["চাঁ", "দ"]);
+    assert_eq!(graphemes("a ক").collect::<Vec<_>>(), ["a", " ", "ক"]);
+    assert_eq!(graphemes("").count(), 0);
+}
+
+#[test]
+fn normalizes_nukta_letters() {
+    let composed = "\u{09AC}\u{09DC}\u{09DF}";
+    let decomposed = "\u{09AC}\u{09A1}\u{09BC}\u{09AF}\u{09BC}";
+    assert_eq!(normalize(composed), decomposed);
+    assert_eq!(normalize("\u{09DD}"), "\u{09A2}\u{09BC}");
+    assert!(matches!(
+        normalize(decomposed),
+        std::borrow::Cow::Borrowed(_)
+    ));
+    assert!(matches!(normalize("কলম"), std::borrow::Cow::Borrowed(_)));
+}
+
+#[test]
+fn checks_words() {
+    let checker = SpellChecker::bundled();
+    assert!(checker.is_correct("আমি"));
+    assert!(checker.is_correct("বাংলা"));
+    assert!(!checker.is_correct("আমী"));
+
+    let checker = word_list("# comment\n\n  বড়  \n");
+    assert!(checker.is_correct("\u{09AC}\u{09DC}"));
+    assert!(checker.is_correct("\u{09AC}\u{09A1}\u{09BC}"));
+    assert!(!checker.is_correct("# comment"));
+    assert!(!checker.is_correct(""));
+}
+
+#[test]
+fn finds_misspellings_in_text() {
+    let checker = word_list("আমি\nভাত\nখাই\n");
+    let text = "আমি ভাথ খাই, hello অমি।";
+    assert_eq!(
+        checker.check_text(text),
+        [
+            Misspelling {
+                word: "ভাথ",
+                start: text.find("ভাথ").unwrap(),
+            },
+            Misspelling {
+                word: "অমি",
+                start: text.find("অমি").unwrap(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn counts_graphemes_as_single_edits() {
+    let checker = word_list("রক্ত\nরত্ন\nরথ\n");
+    // ক্ত and ত্ন are a single substitution, ranked with the phonetic distance of rkt and rtn.
+    assert_eq!(
+        suggest(&checker, "রক্ত", None),
+        [entry("রথ", 2 + 1), entry("রত্ন", 2 + 2)]
+    );
+}
+
+#[test]
+fn counts_transpositions_as_single_edits() {
+    let checker = word_list("কলম\nলকম\nকমল\nমলক\n");
+    assert_eq!(
+        suggest(&checker, "কলম", None),
+        [entry("কমল", 2 + 1), entry("লকম", 2 + 1)]
+    );
+}
+
+#[test]
+fn keeps_suggestions_close() {
+    let checker = word_list("কলম\nকলমদানি\nমন\n");
+    assert_eq!(suggest(&checker, "কলন", None), [entry("কলম", 2 + 1)]);
+    assert!(suggest(&checker, "কলম", None).is_empty());
+    assert!(suggest(&checker, "", None).is_empty());
+}
+
+#[test]
+fn suggests_words_sounding_alike() {
+    // সব and ষভ differ in more graphemes than a short word allows, but sound alike.
+    let checker = word_list("ষভ\nকভ\n");
+    assert_eq!(suggest(&checker, "সব", None), [entry("ষভ", 2 * 2)]);
+
+    // A nukta turns ড into a flap and য into য়.
+    let checker = word_list("বর\nবয়\nবজ\n");
+    assert_eq!(
+        suggest(&checker, "বড়", None),
+        [entry("বর", 2), entry("বজ", 2 + 1), entry("বয়", 2 + 1)]
+    );
+}
+
+#[test]
+fn roman_input_steers_suggestions() {
+    let checker = word_list("কাল\nকলা\n");
+    assert_eq!(
+        suggest(&checker, "কল", None),
+        [entry("কলা", 2 + 1), entry("কাল", 2 + 1)]
+    );
+    assert_eq!(
+        suggest(&checker, "কল", Some("kaal")),
+        [entry("কাল", 2), entry("কলা", 2 + 1)]
+    );
+}
+
+#[test]
+fn limits_suggestions() {
+    let checker = word_list("কাল\nকলা\nখল\n");
+    let suggestions = checker.suggest("কল", None, 1);
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(checker.suggest("কল", None, 0), []);
+}
+
+#[test]
+fn phonetic_keys() {
+    let checker = word_list("সকাল\nশখাল\nষকাল\nসখল\nবাড়ি\nবারি\nভাঢ়ি\nযাই\nজাই\nযায়\n");
+    assert_eq!(read_as(&checker, "shokal"), ["শখাল", "ষকাল", "সকাল"]);
+    assert_eq!(read_as(&checker, "SHOKAL"), ["শখাল", "ষকাল", "সকাল"]);
+    assert_eq!(read_as(&checker, "bari"), ["বাড়ি", "বারি", "ভাঢ়ি"]);
+    assert_eq!(read_as(&checker, "vaarhee"), ["বাড়ি", "বারি", "ভাঢ়ি"]);
+    assert_eq!(read_as(&checker, "zai"), ["জাই", "যাই"]);
+    assert_eq!(read_as(&checker, "jay"), ["যায়"]);
+    assert_eq!(read_as(&checker, "sokol"), ["সখল"]);
+    assert!(read_as(&checker, "kolom").is_empty());
+}
+
+#[test]
+fn reads_hunspell_dictionaries() {
+    let mut checker = SpellChecker::default();
+    checker.extend_from_dic("\u{FEFF}3\nআমি\nবাড়ি/ABC\nনদী/D\tpo:noun\n\n");
+    assert!(checker.is_correct("আমি"));
+    assert!(checker.is_correct("বাড়ি"));
+    assert!(checker.is_correct("নদী"));
+    assert!(!checker.is_correct("3"));
+    assert!(!checker.is_correct("বাড়ি/ABC"));
+}
+
+#[test]
+fn suggests_fixes_for_common_misspellings() {
+    let checker = SpellChecker::bundled();
+    for (misspelled, roman, fix) in [
+        ("পৃথিবি", "prithibi", "পৃথিবী"),
+        ("শ্রেনী", "shreni", "শ্রেণি"),
+        ("সরকারী", "sorkari", "সরকারি"),
+        ("মুহুর্ত", "muhurto", "মুহূর্ত"),
+        ("উজ্বল", "ujjol", "উজ্জ্বল"),
+        ("দুরত্ব", "durotto", "দূরত্ব"),
+        ("বাড়ী", "bari", "বাড়ি"),
+        ("ভাল", "bhalo", "ভালো"),
+        ("জাতিয়", "jatiyo", "জাতীয়"),
+        ("ইতিমধ্য", "itimoddhe", "ইতিমধ্যে"),
+    ] {
+        assert!(!checker.is_correct(misspelled), "{misspelled}");
+        for roman in [None, Some(roman)] {
+            let suggestions = suggest(&checker, misspelled, roman);
+            assert_eq!(
+                suggestions.first().map(|(word, _)| word.as_str()),
+                Some(fix),
+                "{misspelled} {roman:?}: {suggestions:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn offers_bundled_words_sounding_alike() {
+    let checker = SpellChecker::bundled();
+    for (word, roman, alternate) in [("কি", "ki", "কী"), ("কাল", "kal", "খাল")]
+    {
+        let alternates = checker
+            .alternates(word, Some(roman), 5)
+            .into_iter()
+            .map(|alternate| alternate.word)
+            .collect::<Vec<_>>();
+        assert!(
+            alternates.iter().any(|a| a == alternate),
+            "{word}: {alternates:?}"
+        );
+    }
+}
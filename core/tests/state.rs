@@ -0,0 +1,222 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! A harness standing in for TSF, calling back into an engine from within calls made to it.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
+
+use uo_keyboard_core::state::StateCell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Callback {
+    EndEdit,
+    CompositionTerminated,
+    KeyDown(char),
+    Refresh,
+}
+
+/// Fires scheduled callbacks during the next call out, in order.
+#[derive(Debug, Default)]
+struct Host {
+    scheduled: RefCell<VecDeque<Callback>>,
+    text: RefCell<String>,
+}
+
+#[derive(Debug, Default)]
+struct Composition {
+    input: String,
+    committed: String,
+    end_edits: usize,
+    /// Reused for the text set in the document.
+    buffer: String,
+}
+
+#[derive(Debug, Default)]
+struct Engine {
+    state: StateCell<Composition>,
+    host: Host,
+    /// Callbacks that found the state busy.
+    missed: Cell<usize>,
+}
+
+impl Engine {
+    fn new() -> Self {
+        let engine = Engine::default();
+        engine.state.set(Composition::default()).unwrap();
+        engine
+    }
+
+    fn schedule(&self, callbacks: impl IntoIterator<Item = Callback>) {
+        self.host.scheduled.borrow_mut().extend(callbacks);
+    }
+
+    /// Sets the document text, like `ITfRange::SetText` may call back before returning.
+    fn call_out(&self, text: &str) {
+        self.host.text.replace(text.to_owned());
+        loop {
+            let callback = self.host.scheduled.borrow_mut().pop_front();
+            let Some(callback) = callback else {
+                break;
+            };
+            self.callback(callback);
+        }
+    }
+
+    fn callback(&self, callback: Callback) {
+        let handled = match callback {
+            Callback::EndEdit => self.state.with(|s| s.end_edits += 1),
+            Callback::CompositionTerminated => self.finish(),
+            Callback::KeyDown(ch) => self.key_down(ch),
+            Callback::Refresh => self.refresh(),
+        };
+        if handled.is_none() {
+            self.missed.set(self.missed.get() + 1);
+        }
+    }
+
+    fn key_down(&self, ch: char) -> Option<()> {
+        let text = self.state.with(|s| {
+            s.input.push(ch);
+            s.input.to_uppercase()
+        })?;
+        self.call_out(&text);
+        Some(())
+    }
+
+    fn finish(&self) -> Option<()> {
+        self.state.with(|s| {
+            let input = std::mem::take(&mut s.input);
+            s.committed.push_str(&input.to_uppercase());
+        })?;
+        self.call_out("");
+        Some(())
+    }
+
+    /// Sets the document text again from a buffer lent out of the state.
+    fn refresh(&self) -> Option<()> {
+        self.state.with_lent(
+            |s| {
+                let mut buffer = std::mem::take(&mut s.buffer);
+                buffer.clear();
+                buffer.push_str(&s.input.to_uppercase());
+                buffer
+            },
+            |buffer| self.call_out(buffer),
+            |s, buffer| s.buffer = buffer,
+        )
+    }
+
+    /// The pattern the cell guards against: calling out while holding the state.
+    fn key_down_holding_state(&self, ch: char) -> Option<()> {
+        self.state.with(|s| {
+            s.input.push(ch);
+            self.call_out(&s.input.to_uppercase());
+        })
+    }
+
+    fn composition<R>(&self, f: impl FnOnce(&Composition) -> R) -> R {
+        self.state.with(|s| f(s)).unwrap()
+    }
+}
+
+#[test]
+fn callbacks_mid_operation_see_the_state() {
+    let engine = Engine::new();
+
+    engine.schedule([Callback::EndEdit]);
+    engine.key_down('a');
+    engine.schedule([Callback::EndEdit, Callback::EndEdit]);
+    engine.key_down('b');
+
+    assert_eq!(engine.missed.get(), 0);
+    assert_eq!(engine.composition(|c| c.end_edits), 3);
+    assert_eq!(*engine.host.text.borrow(), "AB");
+}
+
+#[test]
+fn nested_key_events_apply_in_order() {
+    let engine = Engine::new();
+
+    engine.schedule([Callback::KeyDown('m'), Callback::KeyDown('i')]);
+    engine.key_down('a');
+
+    assert_eq!(engine.missed.get(), 0);
+    assert_eq!(engine.composition(|c| c.input.clone()), "ami");
+    assert_eq!(*engine.host.text.borrow(), "AMI");
+}
+
+#[test]
+fn termination_mid_update() {
+    let engine = Engine::new();
+
+    engine.key_down('a');
+    engine.schedule([Callback::CompositionTerminated, Callback::EndEdit]);
+    engine.key_down('b');
+
+    assert_eq!(engine.missed.get(), 0);
+    assert_eq!(engine.composition(|c| c.committed.clone()), "AB");
+    assert_eq!(engine.composition(|c| c.input.clone()), "");
+    assert_eq!(engine.composition(|c| c.end_edits), 1);
+}
+
+#[test]
+fn state_held_across_a_call_out_is_busy_not_a_panic() {
+    let engine = Engine::new();
+
+    engine.schedule([Callback::EndEdit, Callback::KeyDown('b')]);
+    assert_eq!(engine.key_down_holding_state('a'), Some(()));
+
+    assert_eq!(engine.missed.get(), 2);
+    assert_eq!(engine.composition(|c| c.input.clone()), "a");
+}
+
+#[test]
+fn lent_values_are_handed_back() {
+    let engine = Engine::new();
+
+    engine.key_down('a');
+    engine.host.text.replace(String::new());
+    engine.schedule([Callback::EndEdit, Callback::KeyDown('b')]);
+    assert_eq!(engine.refresh(), Some(()));
+
+    assert_eq!(engine.missed.get(), 0);
+    assert_eq!(*engine.host.text.borrow(), "AB");
+    assert_eq!(engine.composition(|c| c.buffer.clone()), "A");
+    assert_eq!(engine.composition(|c| c.end_edits), 1);
+}
+
+#[test]
+fn lending_from_a_busy_state_fails() {
+    let engine = Engine::new();
+
+    engine.key_down('a');
+    engine.schedule([Callback::Refresh]);
+    assert_eq!(engine.key_down_holding_state('b'), Some(()));
+    assert_eq!(engine.missed.get(), 1);
+
+    engine.host.text.replace(String::new());
+    assert_eq!(engine.state.with(|_| engine.refresh()), Some(None));
+    assert_eq!(*engine.host.text.borrow(), "");
+    assert_eq!(engine.composition(|c| c.input.clone()), "ab");
+}
+
+#[test]
+fn set_and_take_while_busy() {
+    let cell = StateCell::new();
+    assert_eq!(cell.with(|n: &mut i32| *n), None);
+    assert_eq!(cell.set(1), Ok(()));
+
+    cell.with(|n| {
+        assert!(cell.is_busy());
+        assert_eq!(cell.set(2), Err(2));
+        assert_eq!(cell.take(), None);
+        *n += 10;
+    });
+
+    assert!(!cell.is_busy());
+    assert_eq!(cell.take(), Some(11));
+    assert_eq!(cell.with(|n| *n), None);
+}
@@ -0,0 +1,77 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::text::{Utf16Diff, utf16_diff};
+
+fn diff(old: &str, new: &str) -> Utf16Diff {
+    let old = old.encode_utf16().collect::<Vec<_>>();
+    let new = new.encode_utf16().collect::<Vec<_>>();
+    let diff = utf16_diff(&old, &new);
+
+    // Applying the diff must always give the new text.
+    let mut applied = old.clone();
+    applied.splice(
+        diff.start..diff.start + diff.old_len,
+        new[diff.start..diff.start + diff.new_len].iter().copied(),
+    );
+    assert_eq!(applied, new);
+
+    diff
+}
+
+fn span(start: usize, old_len: usize, new_len: usize) -> Utf16Diff {
+    Utf16Diff {
+        start,
+        old_len,
+        new_len,
+    }
+}
+
+#[test]
+fn unchanged() {
+    assert!(diff("", "").is_empty());
+    assert!(diff("আমি", "আমি").is_empty());
+}
+
+#[test]
+fn append_and_delete() {
+    assert_eq!(diff("", "আ"), span(0, 0, 1));
+    assert_eq!(diff("আম", "আমর"), span(2, 0, 1));
+    assert_eq!(diff("আমর", "আম"), span(2, 1, 0));
+    assert_eq!(diff("আম", ""), span(0, 2, 0));
+}
+
+#[test]
+fn replace_in_middle() {
+    assert_eq!(diff("তুমি আমি", "তুমি তোমি"), span(5, 1, 2));
+    assert_eq!(diff("ab", "ba"), span(0, 2, 2));
+}
+
+#[test]
+fn repeated_text() {
+    // The shared suffix can't overlap the shared prefix.
+    assert_eq!(diff("কক", "ককক"), span(2, 0, 1));
+    assert_eq!(diff("ককক", "কক"), span(2, 1, 0));
+}
+
+#[test]
+fn combining_marks() {
+    // A vowel sign is replaced along with its letter.
+    assert_eq!(diff("ক", "কা"), span(0, 1, 2));
+    assert_eq!(diff("কি", "কী"), span(0, 2, 2));
+    assert_eq!(diff("আমি", "আমিন"), span(3, 0, 1));
+    assert_eq!(diff("কাক", "কক"), span(0, 2, 1));
+
+    // So is the rest of a conjunct.
+    assert_eq!(diff("ক্", "ক্ষ"), span(0, 2, 3));
+    assert_eq!(diff("অক্ষর", "অক্সর"), span(1, 3, 3));
+}
+
+#[test]
+fn surrogate_pairs() {
+    assert_eq!(diff("😄", "😁"), span(0, 2, 2));
+    assert_eq!(diff("a😄", "a😄😁"), span(3, 0, 2));
+    assert_eq!(diff("😄b", "😁b"), span(0, 2, 2));
+    // Both emoji share the high surrogate.
+    assert_eq!(diff("x😄y", "x😁y"), span(1, 2, 2));
+}
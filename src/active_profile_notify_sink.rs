@@ -6,14 +6,23 @@ use crate::*;
 impl ITfActiveLanguageProfileNotifySink_Impl for Ime_Impl {
     #[tracing::instrument(skip_all, ret, err)]
     fn OnActivated(&self, clsid: *const GUID, profile: *const GUID, activated: BOOL) -> Result<()> {
-        let clsid = unsafe { clsid.as_ref() }.ok_or(S_OK)?;
-        if activated.as_bool()
-            && *clsid == globals::IME_CLSID
-            && let Some(mut state) = self.state_mut()
-        {
-            let profile = unsafe { profile.as_ref() }.ok_or(S_OK)?;
-            state.transcriber = transcriber::Transcriber::new(*profile);
-        }
-        Ok(())
+        self.contain(|| {
+            let clsid = unsafe { clsid.as_ref() }.ok_or(S_OK)?;
+            if activated.as_bool() && *clsid == globals::IME_CLSID {
+                let profile = unsafe { profile.as_ref() }.ok_or(S_OK)?;
+                let transcriber = transcriber::Transcriber::new(*profile);
+                let context_seeds = transcriber.context_seeds();
+                if self
+                    .with_state(|s| {
+                        s.transcriber = transcriber;
+                        s.context_seeds = context_seeds;
+                    })
+                    .is_some()
+                {
+                    self.update_lang_bar_item();
+                }
+            }
+            Ok(())
+        })
     }
 }
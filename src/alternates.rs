@@ -0,0 +1,305 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use uo_keyboard_core::text::{Token, tokenize};
+
+use crate::*;
+
+const ALTERNATE_LIMIT: usize = 8;
+/// Longest text of a range read for alternates, in UTF-16 units.
+const MAX_TEXT_LEN: usize = 64;
+
+impl ITfFnGetLinguisticAlternates_Impl for Ime_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetAlternates(&self, range: Ref<'_, ITfRange>) -> Result<ITfCandidateList> {
+        self.contain(|| {
+            let range = range.ok()?;
+            let ctx = unsafe { range.GetContext() }?;
+            let client_id = self.with_state(|s| s.client_id).ok_or(E_FAIL)?;
+
+            let session = ReadRangeSession {
+                range: range.clone(),
+                read: RefCell::default(),
+            }
+            .into_object();
+            unsafe {
+                ctx.RequestEditSession(client_id, session.as_interface(), TF_ES_SYNC | TF_ES_READ)
+            }?
+            .ok()?;
+
+            let (text, reading) = session.read.take().ok_or(E_FAIL)?;
+            // At the caret, the alternates are the words likely to be typed next.
+            if text.is_empty() {
+                let predictions = self.next_word_predictions();
+                if predictions.is_empty() {
+                    return Err(E_FAIL.into());
+                }
+                return Ok(CandidateList {
+                    ime: self.to_object(),
+                    ctx,
+                    range: range.clone(),
+                    before: String::new(),
+                    after: String::new(),
+                    alternates: predictions.into(),
+                }
+                .into());
+            }
+
+            let mut tokens = tokenize(&text);
+            let (Some((start, Token::Word(word))), None) = (tokens.next(), tokens.next()) else {
+                return Err(E_FAIL.into());
+            };
+            let alternates = self.linguistic_alternates(word, reading.as_deref());
+
+            Ok(CandidateList {
+                ime: self.to_object(),
+                ctx,
+                range: range.clone(),
+                before: text[..start].to_owned(),
+                after: text[start + word.len()..].to_owned(),
+                alternates: alternates.into(),
+            }
+            .into())
+        })
+    }
+}
+
+impl Ime_Impl {
+    /// Offers words sounding like `word`, typed as `roman` if known, to replace it with.
+    #[tracing::instrument(skip(self), ret)]
+    fn linguistic_alternates(&self, word: &str, roman: Option<&str>) -> Vec<String> {
+        self.with_state(|s| {
+            s.spell_checker
+                .alternates(word, roman, ALTERNATE_LIMIT)
+                .into_iter()
+                .map(|alternate| alternate.word)
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+}
+
+/// Reads the text of a range along with the roman reading stored on it.
+#[implement(ITfEditSession)]
+#[derive(Debug)]
+struct ReadRangeSession {
+    range: ITfRange,
+    read: RefCell<Option<(String, Option<String>)>>,
+}
+
+impl ITfEditSession_Impl for ReadRangeSession_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn DoEditSession(&self, edit_cookie: u32) -> Result<()> {
+        panic_guard::contain(|| {
+            let mut text = vec![0; MAX_TEXT_LEN + 1];
+            let mut len = 0;
+            unsafe { self.range.GetText(edit_cookie, 0, &mut text, &mut len) }?;
+            // A range longer than any word isn't worth offering alternates for.
+            if len as usize > MAX_TEXT_LEN {
+                return Ok(());
+            }
+            let text = String::from_utf16_lossy(&text[..len as usize]);
+
+            let ctx = unsafe { self.range.GetContext() }?;
+            let reading = unsafe { ctx.GetProperty(&GUID_PROP_READING) }
+                .and_then(|prop| unsafe { prop.GetValue(edit_cookie, &self.range) })
+                .ok()
+                .and_then(|value| BSTR::try_from(&value).ok())
+                .map(|reading| reading.to_string())
+                .filter(|reading| !reading.is_empty());
+
+            self.read.replace(Some((text, reading)));
+            Ok(())
+        })
+    }
+}
+
+/// Replaces the text of a range as a correction, keeping the properties stored on it.
+#[implement(ITfEditSession)]
+#[derive(Debug)]
+struct ReplaceRangeSession {
+    range: ITfRange,
+    text: Vec<u16>,
+}
+
+impl ITfEditSession_Impl for ReplaceRangeSession_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn DoEditSession(&self, edit_cookie: u32) -> Result<()> {
+        panic_guard::contain(|| unsafe {
+            self.range
+                .SetText(edit_cookie, TF_ST_CORRECTION, &self.text)
+        })
+    }
+}
+
+/// Alternates of a word in a document, replacing it when one is chosen.
+#[implement(ITfCandidateList)]
+#[derive(Debug)]
+struct CandidateList {
+    ime: ComObject<Ime>,
+    ctx: ITfContext,
+    range: ITfRange,
+    /// Text of the range before and after the word.
+    before: String,
+    after: String,
+    alternates: Rc<[String]>,
+}
+
+impl ITfCandidateList_Impl for CandidateList_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn EnumCandidates(&self) -> Result<IEnumTfCandidates> {
+        panic_guard::contain(|| {
+            Ok(EnumCandidates {
+                alternates: self.alternates.clone(),
+                index: Cell::new(0),
+            }
+            .into())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetCandidate(&self, index: u32) -> Result<ITfCandidateString> {
+        panic_guard::contain(|| {
+            let word = self.alternates.get(index as usize).ok_or(E_INVALIDARG)?;
+            Ok(CandidateString {
+                word: word.clone(),
+                index,
+            }
+            .into())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetCandidateNum(&self) -> Result<u32> {
+        panic_guard::contain(|| Ok(self.alternates.len() as u32))
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn SetResult(&self, index: u32, result: TfCandidateResult) -> Result<()> {
+        panic_guard::contain(|| {
+            let word = self.alternates.get(index as usize).ok_or(E_INVALIDARG)?;
+            if result != CAND_FINALIZED {
+                return Ok(());
+            }
+
+            let client_id = self.ime.with_state(|s| s.client_id).ok_or(E_FAIL)?;
+            let text = format!("{}{word}{}", self.before, self.after);
+            let session = ReplaceRangeSession {
+                range: self.range.clone(),
+                text: text.encode_utf16().collect(),
+            }
+            .into_object();
+            unsafe {
+                self.ctx.RequestEditSession(
+                    client_id,
+                    session.as_interface(),
+                    TF_ES_ASYNCDONTCARE | TF_ES_READWRITE,
+                )
+            }?
+            .ok()
+        })
+    }
+}
+
+#[implement(ITfCandidateString)]
+#[derive(Debug)]
+struct CandidateString {
+    word: String,
+    index: u32,
+}
+
+impl ITfCandidateString_Impl for CandidateString_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetString(&self) -> Result<BSTR> {
+        panic_guard::contain(|| Ok(self.word.as_str().into()))
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetIndex(&self) -> Result<u32> {
+        panic_guard::contain(|| Ok(self.index))
+    }
+}
+
+#[implement(IEnumTfCandidates)]
+#[derive(Debug)]
+struct EnumCandidates {
+    alternates: Rc<[String]>,
+    index: Cell<usize>,
+}
+
+impl IEnumTfCandidates_Impl for EnumCandidates_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Clone(&self) -> Result<IEnumTfCandidates> {
+        panic_guard::contain(|| {
+            Ok(EnumCandidates {
+                alternates: self.alternates.clone(),
+                index: self.index.clone(),
+            }
+            .into())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Next(
+        &self,
+        count: u32,
+        candidates: *mut Option<ITfCandidateString>,
+        fetched: *mut u32,
+    ) -> Result<()> {
+        panic_guard::contain(|| {
+            if candidates.is_null() || (count != 1 && fetched.is_null()) {
+                return E_INVALIDARG.ok();
+            }
+
+            let start = self.index.get();
+            let remaining = &self.alternates[start..];
+            let n = remaining.len().min(count as usize);
+            for (i, word) in remaining[..n].iter().enumerate() {
+                let candidate = CandidateString {
+                    word: word.clone(),
+                    index: (start + i) as u32,
+                };
+                unsafe { candidates.add(i).write(Some(candidate.into())) };
+            }
+            self.index.set(start + n);
+
+            if !fetched.is_null() {
+                unsafe { fetched.write(n as u32) };
+            }
+
+            // `S_FALSE` has to travel as an error, since `Ok` always maps to `S_OK`.
+            if n == count as usize {
+                Ok(())
+            } else {
+                Err(S_FALSE.into())
+            }
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Reset(&self) -> Result<()> {
+        panic_guard::contain(|| {
+            self.index.set(0);
+            Ok(())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Skip(&self, count: u32) -> Result<()> {
+        panic_guard::contain(|| {
+            let index = self.index.get() + count as usize;
+            self.index.set(index.min(self.alternates.len()));
+
+            if index <= self.alternates.len() {
+                Ok(())
+            } else {
+                Err(S_FALSE.into())
+            }
+        })
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright 2026 Muhammad Ragib Hasin
 // SPDX-License-Identifier: MPL-2.0
 
+use windows::Win32::System::Variant::VARIANT;
+
 use crate::*;
 
 #[derive(Debug, Clone)]
@@ -37,6 +39,37 @@ impl Compartment {
             Ok(i32::try_from(&variant)? as u32)
         }
     }
+
+    /// Whether the compartment holds any value yet.
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn is_set(&self) -> Result<bool> {
+        let variant = unsafe { self.get_compartment()?.GetValue() }?;
+        Ok(!variant.is_empty())
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn set_bool(&self, client_id: u32, value: bool) -> Result<()> {
+        self.set_u32(client_id, value as u32)
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn set_u32(&self, client_id: u32, value: u32) -> Result<()> {
+        // Windows expects i32 for compartment.
+        let variant = VARIANT::from(value as i32);
+        unsafe { self.get_compartment()?.SetValue(client_id, &variant) }
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn advise_sink(&self, sink: &IUnknown) -> Result<u32> {
+        let source = self.get_compartment()?.cast::<ITfSource>()?;
+        unsafe { source.AdviseSink(&ITfCompartmentEventSink::IID, sink) }
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn unadvise_sink(&self, cookie: u32) -> Result<()> {
+        let source = self.get_compartment()?.cast::<ITfSource>()?;
+        unsafe { source.UnadviseSink(cookie) }
+    }
 }
 
 // #[tracing::instrument(skip_all, ret)]
@@ -45,3 +78,40 @@ pub(crate) fn read_bool(thread_mgr: &ITfThreadMgr, guid: GUID) -> bool {
         .get_bool()
         .unwrap_or(false)
 }
+
+// #[tracing::instrument(skip_all, ret, err)]
+pub(crate) fn write_bool(
+    thread_mgr: &ITfThreadMgr,
+    client_id: u32,
+    guid: GUID,
+    value: bool,
+) -> Result<()> {
+    Compartment::new(thread_mgr, guid).set_bool(client_id, value)
+}
+
+/// Writes `value` unless the compartment already holds one, e.g. set by the user or another
+/// text service in this thread.
+// #[tracing::instrument(skip_all, ret, err)]
+pub(crate) fn init_bool(
+    thread_mgr: &ITfThreadMgr,
+    client_id: u32,
+    guid: GUID,
+    value: bool,
+) -> Result<()> {
+    let compartment = Compartment::new(thread_mgr, guid);
+    if compartment.is_set()? {
+        return Ok(());
+    }
+    compartment.set_bool(client_id, value)
+}
+
+/// Advises `sink` as an `ITfCompartmentEventSink` of the compartment, returning the cookie.
+// #[tracing::instrument(skip_all, ret, err)]
+pub(crate) fn advise_sink(thread_mgr: &ITfThreadMgr, guid: GUID, sink: &IUnknown) -> Result<u32> {
+    Compartment::new(thread_mgr, guid).advise_sink(sink)
+}
+
+// #[tracing::instrument(skip_all, ret, err)]
+pub(crate) fn unadvise_sink(thread_mgr: &ITfThreadMgr, guid: GUID, cookie: u32) -> Result<()> {
+    Compartment::new(thread_mgr, guid).unadvise_sink(cookie)
+}
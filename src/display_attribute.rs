@@ -0,0 +1,214 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::cell::Cell;
+
+use windows::Win32::Foundation::COLORREF;
+
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DisplayAttribute {
+    /// Roman input still being typed.
+    Input,
+    /// Text replaced by a conversion, e.g. a shortcode or a selected candidate.
+    Converted,
+}
+
+impl DisplayAttribute {
+    const ALL: [Self; 2] = [Self::Input, Self::Converted];
+
+    fn from_guid(guid: &GUID) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|attribute| attribute.guid() == *guid)
+    }
+
+    fn guid(self) -> GUID {
+        match self {
+            Self::Input => globals::IME_DISPLAY_ATTRIBUTE_INPUT,
+            Self::Converted => globals::IME_DISPLAY_ATTRIBUTE_CONVERTED,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Input => "Ũõ Keyboard input",
+            Self::Converted => "Ũõ Keyboard converted",
+        }
+    }
+
+    fn info(self) -> TF_DISPLAYATTRIBUTE {
+        let (line_style, bold_line, attr) = match self {
+            Self::Input => (TF_LS_DOT, FALSE, TF_ATTR_INPUT),
+            Self::Converted => (TF_LS_SOLID, TRUE, TF_ATTR_TARGET_CONVERTED),
+        };
+        let no_color = TF_DA_COLOR {
+            r#type: TF_CT_NONE,
+            Anonymous: TF_DA_COLOR_0 { cr: COLORREF(0) },
+        };
+
+        TF_DISPLAYATTRIBUTE {
+            crText: no_color,
+            crBk: no_color,
+            lsStyle: line_style,
+            fBoldLine: bold_line,
+            crLine: no_color,
+            bAttr: attr,
+        }
+    }
+}
+
+/// Atoms of the display attribute GUIDs, which are the values stored in `GUID_PROP_ATTRIBUTE`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DisplayAttributeAtoms([u32; DisplayAttribute::ALL.len()]);
+
+impl DisplayAttributeAtoms {
+    #[tracing::instrument(ret, err)]
+    pub(crate) fn register() -> Result<Self> {
+        let mgr = utils::create_instance_inproc::<ITfCategoryMgr>(&CLSID_TF_CategoryMgr)?;
+
+        let mut atoms = [0; DisplayAttribute::ALL.len()];
+        for (atom, attribute) in atoms.iter_mut().zip(DisplayAttribute::ALL) {
+            *atom = unsafe { mgr.RegisterGUID(&attribute.guid()) }?;
+        }
+
+        Ok(Self(atoms))
+    }
+
+    pub(crate) fn get(&self, attribute: DisplayAttribute) -> u32 {
+        self.0[attribute as usize]
+    }
+}
+
+#[implement(ITfDisplayAttributeInfo)]
+#[derive(Debug)]
+struct DisplayAttributeInfo {
+    attribute: DisplayAttribute,
+}
+
+impl ITfDisplayAttributeInfo_Impl for DisplayAttributeInfo_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetGUID(&self) -> Result<GUID> {
+        panic_guard::contain(|| Ok(self.attribute.guid()))
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetDescription(&self) -> Result<BSTR> {
+        panic_guard::contain(|| Ok(self.attribute.description().into()))
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetAttributeInfo(&self, info: *mut TF_DISPLAYATTRIBUTE) -> Result<()> {
+        panic_guard::contain(|| {
+            if info.is_null() {
+                return E_INVALIDARG.ok();
+            }
+            unsafe { info.write(self.attribute.info()) };
+            Ok(())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn SetAttributeInfo(&self, _: *const TF_DISPLAYATTRIBUTE) -> Result<()> {
+        panic_guard::contain(|| E_NOTIMPL.ok())
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Reset(&self) -> Result<()> {
+        panic_guard::contain(|| Ok(()))
+    }
+}
+
+#[implement(IEnumTfDisplayAttributeInfo)]
+#[derive(Debug, Default)]
+struct EnumDisplayAttributeInfo {
+    index: Cell<usize>,
+}
+
+impl IEnumTfDisplayAttributeInfo_Impl for EnumDisplayAttributeInfo_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Clone(&self) -> Result<IEnumTfDisplayAttributeInfo> {
+        panic_guard::contain(|| {
+            Ok(EnumDisplayAttributeInfo {
+                index: self.index.clone(),
+            }
+            .into())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Next(
+        &self,
+        count: u32,
+        infos: *mut Option<ITfDisplayAttributeInfo>,
+        fetched: *mut u32,
+    ) -> Result<()> {
+        panic_guard::contain(|| {
+            if infos.is_null() || (count != 1 && fetched.is_null()) {
+                return E_INVALIDARG.ok();
+            }
+
+            let remaining = &DisplayAttribute::ALL[self.index.get()..];
+            let n = remaining.len().min(count as usize);
+            for (i, &attribute) in remaining[..n].iter().enumerate() {
+                unsafe {
+                    infos
+                        .add(i)
+                        .write(Some(DisplayAttributeInfo { attribute }.into()))
+                };
+            }
+            self.index.set(self.index.get() + n);
+
+            if !fetched.is_null() {
+                unsafe { fetched.write(n as u32) };
+            }
+
+            // `S_FALSE` has to travel as an error, since `Ok` always maps to `S_OK`.
+            if n == count as usize {
+                Ok(())
+            } else {
+                Err(S_FALSE.into())
+            }
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Reset(&self) -> Result<()> {
+        panic_guard::contain(|| {
+            self.index.set(0);
+            Ok(())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Skip(&self, count: u32) -> Result<()> {
+        panic_guard::contain(|| {
+            let index = self.index.get() + count as usize;
+            self.index.set(index.min(DisplayAttribute::ALL.len()));
+
+            if index <= DisplayAttribute::ALL.len() {
+                Ok(())
+            } else {
+                Err(S_FALSE.into())
+            }
+        })
+    }
+}
+
+impl ITfDisplayAttributeProvider_Impl for Ime_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn EnumDisplayAttributeInfo(&self) -> Result<IEnumTfDisplayAttributeInfo> {
+        self.contain(|| Ok(EnumDisplayAttributeInfo::default().into()))
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetDisplayAttributeInfo(&self, guid: *const GUID) -> Result<ITfDisplayAttributeInfo> {
+        self.contain(|| {
+            let guid = unsafe { guid.as_ref() }.ok_or(E_INVALIDARG)?;
+            DisplayAttribute::from_guid(guid)
+                .map(|attribute| DisplayAttributeInfo { attribute }.into())
+                .ok_or(E_INVALIDARG.into())
+        })
+    }
+}
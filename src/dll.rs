@@ -11,7 +11,7 @@ use crate::*;
 static DLL_INSTANCE: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
 static mut CS: CRITICAL_SECTION = unsafe { std::mem::zeroed() };
 
-fn instance_handle() -> HMODULE {
+pub(crate) fn instance_handle() -> HMODULE {
     HMODULE(DLL_INSTANCE.load(Relaxed))
 }
 
@@ -29,25 +29,29 @@ unsafe extern "system" fn DllRegisterServer() -> HRESULT {
         Ok(())
     }
 
-    // See https://github.com/rust-lang/rust-clippy/issues/13185
-    #[allow(clippy::manual_inspect)]
-    register()
-        .map_err(|err| {
-            let _ = unsafe { DllUnregisterServer() };
-            err
-        })
-        .into()
+    panic_guard::contain_hresult(|| {
+        // See https://github.com/rust-lang/rust-clippy/issues/13185
+        #[allow(clippy::manual_inspect)]
+        register()
+            .map_err(|err| {
+                let _ = unsafe { DllUnregisterServer() };
+                err
+            })
+            .into()
+    })
 }
 
 #[unsafe(no_mangle)]
 #[allow(non_snake_case)]
 #[doc(hidden)]
 unsafe extern "system" fn DllUnregisterServer() -> HRESULT {
-    registration::unregister_profile().ok();
-    registration::unregister_categories().ok();
-    registration::unregister_server().ok();
-    tracing::trace!("unregistration complete");
-    S_OK
+    panic_guard::contain_hresult(|| {
+        registration::unregister_profile().ok();
+        registration::unregister_categories().ok();
+        registration::unregister_server().ok();
+        tracing::trace!("unregistration complete");
+        S_OK
+    })
 }
 
 #[allow(non_snake_case)]
@@ -57,29 +61,34 @@ unsafe extern "system" fn DllMain(
     reason: u32,
     _lpv_reserved: *mut std::ffi::c_void,
 ) -> BOOL {
-    match reason {
-        DLL_PROCESS_ATTACH => {
-            DLL_INSTANCE.store(instance.0, Relaxed);
-            if (unsafe { InitializeCriticalSectionAndSpinCount(&raw mut CS, 0) }).is_err() {
-                return false.into();
+    panic_guard::contain(|| {
+        match reason {
+            DLL_PROCESS_ATTACH => {
+                DLL_INSTANCE.store(instance.0, Relaxed);
+                unsafe { InitializeCriticalSectionAndSpinCount(&raw mut CS, 0) }?;
+                std::panic::set_hook(Box::new(tracing_panic::panic_hook));
+                init_tracing();
+                tracing::trace!("Uo Bangla Keyboard loaded");
             }
-            std::panic::set_hook(Box::new(tracing_panic::panic_hook));
-            let trace_filter = tracing_subscriber::EnvFilter::new("error,uo_keyboard=trace");
-            tracing_subscriber::registry()
-                .with(
-                    tracing_etw::LayerBuilder::new("UoBanglaKeyboard")
-                        .build()
-                        .unwrap()
-                        .with_filter(trace_filter),
-                )
-                .init();
-            tracing::trace!("Uo Bangla Keyboard loaded");
-        }
-        DLL_PROCESS_DETACH => {
-            unsafe { DeleteCriticalSection(&raw mut CS) };
-            tracing::trace!("Uo Bangla Keyboard unloaded");
+            DLL_PROCESS_DETACH => {
+                unsafe { DeleteCriticalSection(&raw mut CS) };
+                tracing::trace!("Uo Bangla Keyboard unloaded");
+            }
+            _ => {}
         }
-        _ => {}
+        Ok(())
+    })
+    .is_ok()
+    .into()
+}
+
+/// Sends traces to ETW, going without them if the provider can't be registered.
+fn init_tracing() {
+    let trace_filter = tracing_subscriber::EnvFilter::new("error,uo_keyboard=trace");
+    if let Ok(layer) = tracing_etw::LayerBuilder::new("UoBanglaKeyboard").build() {
+        tracing_subscriber::registry()
+            .with(layer.with_filter(trace_filter))
+            .try_init()
+            .ok();
     }
-    true.into()
 }
@@ -1,13 +1,31 @@
 // Copyright 2026 Muhammad Ragib Hasin
 // SPDX-License-Identifier: MPL-2.0
 
+use std::cell::Cell;
+
+use uo_keyboard_core::{
+    commit::{CommitAction, Terminator},
+    context,
+    ops::{self, EditOp, LockMode, LockResponse},
+    phrase::PhraseCandidates,
+    shortcode::Shortcode,
+    text,
+};
+
 use crate::*;
 
 #[derive(Debug)]
 pub(crate) struct Composition {
     pub(crate) tf_composition: ITfComposition,
     ctx: ITfContext,
+    /// Keys as typed, before the transcriber adapts them.
+    typed: String,
     input: String,
+    /// Whether the text is the input as typed, rather than its conversion.
+    literal: bool,
+    /// Candidates for the conversion once asked for, the text being the selected one, until
+    /// the input changes.
+    candidates: Option<PhraseCandidates>,
 }
 
 #[derive(Debug)]
@@ -16,6 +34,17 @@ struct EditSession {
     ctx: ITfContext,
 }
 
+impl ActiveImeState {
+    /// The input of the composition, or of the keys typed without one.
+    pub(crate) fn composition_input(&self) -> Option<&str> {
+        match &self.composition {
+            Some(composition) => Some(&composition.input),
+            None if !self.direct_input.is_empty() => Some(self.direct_input.input()),
+            None => None,
+        }
+    }
+}
+
 impl EditSession {
     fn new(ime: &Ime_Impl, ctx: &ITfContext) -> Self {
         EditSession {
@@ -24,9 +53,20 @@ impl EditSession {
         }
     }
 
+    /// Whether keys are typed straight into the document, without a composition.
+    fn uses_direct_input(&self) -> bool {
+        let doc_mgr = unsafe { self.ctx.GetDocumentMgr() }.ok();
+        self.ime
+            .with_state(|s| {
+                s.uses_direct_input
+                    || s.refused_composition.is_some() && s.refused_composition == doc_mgr
+            })
+            .unwrap_or(false)
+    }
+
     #[tracing::instrument(skip_all, ret, err)]
     fn start_composition(&self, edit_cookie: u32) -> Result<()> {
-        if self.ime.composition().is_some() {
+        if self.ime.with_state(|s| s.composition.is_some()) != Some(false) {
             tracing::trace!("composition already exists");
             return Ok(());
         }
@@ -34,11 +74,27 @@ impl EditSession {
         let ias = self.ctx.cast::<ITfInsertAtSelection>()?;
         let insert_range =
             unsafe { ias.InsertTextAtSelection(edit_cookie, TF_IAS_QUERYONLY, &[]) }?;
+        // Not every document lets the text be read, in which case there is no context.
+        let left_context = self
+            .text_before(edit_cookie, &insert_range, context::CONTEXT_LEN)
+            .unwrap_or_default();
 
-        let ctx_composition = self.ctx.cast::<ITfContextComposition>()?;
-        let composition = unsafe {
-            ctx_composition.StartComposition(edit_cookie, &insert_range, self.ime.as_interface())
-        }?;
+        let composition = match self
+            .ctx
+            .cast::<ITfContextComposition>()
+            .and_then(|ctx| unsafe {
+                ctx.StartComposition(edit_cookie, &insert_range, self.ime.as_interface())
+            }) {
+            Ok(composition) => composition,
+            // Keys are typed straight into the document until the focus moves, rather than lost.
+            Err(e) => {
+                tracing::warn!(%e, "composition refused, falling back to direct input");
+                let doc_mgr = unsafe { self.ctx.GetDocumentMgr() }?;
+                self.ime
+                    .with_state(|s| s.refused_composition = Some(doc_mgr));
+                return Ok(());
+            }
+        };
 
         utils::set_selection(
             edit_cookie,
@@ -52,31 +108,78 @@ impl EditSession {
             },
         )?;
 
-        self.ime.state_mut().unwrap().composition = Some(Composition {
-            tf_composition: composition,
-            ctx: self.ctx.clone(),
-            input: String::new(),
-        });
+        self.ime
+            .with_state(|s| {
+                s.preedit.clear();
+                if let Some(seed) = s.context_seeds.seed_for(&left_context) {
+                    s.preedit.set_seed(seed, |input, output| {
+                        s.transcriber.convert_into(input, output)
+                    });
+                }
+                s.composition = Some(Composition {
+                    tf_composition: composition,
+                    ctx: self.ctx.clone(),
+                    typed: String::new(),
+                    input: String::new(),
+                    literal: false,
+                    candidates: None,
+                });
+            })
+            .ok_or(E_UNEXPECTED.into())
+    }
 
-        Ok(())
+    /// Reads up to `len` UTF-16 units of text before `range`.
+    #[tracing::instrument(skip(self, range), ret, err)]
+    fn text_before(&self, edit_cookie: u32, range: &ITfRange, len: usize) -> Result<String> {
+        let left = self.range_before(edit_cookie, range, len)?;
+        let mut text = vec![0; len];
+        let mut len = 0;
+        unsafe { left.GetText(edit_cookie, 0, &mut text, &mut len) }?;
+        Ok(String::from_utf16_lossy(&text[..len as usize]))
+    }
+
+    /// The range of up to `len` UTF-16 units before `range`.
+    fn range_before(&self, edit_cookie: u32, range: &ITfRange, len: usize) -> Result<ITfRange> {
+        let left = unsafe { range.Clone() }?;
+        unsafe { left.Collapse(edit_cookie, TF_ANCHOR_START) }?;
+        let mut shifted = 0;
+        unsafe { left.ShiftStart(edit_cookie, -(len as i32), &mut shifted, std::ptr::null()) }?;
+        Ok(left)
     }
 
     #[tracing::instrument(skip_all, ret, err)]
     fn terminate_composition(&self, edit_cookie: u32) -> Result<()> {
-        let Some(composition) = self.ime.state_mut().unwrap().composition.take() else {
+        let Some((composition, output)) = self
+            .ime
+            .with_state(|s| Some((s.composition.take()?, s.preedit.text().to_owned())))
+            .flatten()
+        else {
             tracing::trace!("composition doesn't exist");
             return Ok(());
         };
 
+        // Text reverted to roman isn't a word to reopen, and ends the context of predictions.
+        self.ime.with_state(|s| {
+            if !composition.literal {
+                s.recent_commits.push(&composition.typed, &output);
+            }
+            s.word_history.push_text(&output);
+        });
+
         let selection = utils::get_selection(edit_cookie, &self.ctx, TF_DEFAULT_SELECTION)
             .map_err(|e| Error::new(e.code(), "failed to get selection"))?;
-        let selection_range = selection.range.as_ref().unwrap();
+        let selection_range = selection.range.as_ref().ok_or(E_UNEXPECTED)?;
 
         unsafe { selection_range.Collapse(edit_cookie, TF_ANCHOR_END) }
             .map_err(|e| Error::new(e.code(), "failed to collapse selection"))?;
         utils::set_selection(edit_cookie, &self.ctx, selection)
             .map_err(|e| Error::new(e.code(), "failed to set selection"))?;
 
+        // Committed text must not keep the composition styling.
+        let range = unsafe { composition.tf_composition.GetRange() }?;
+        self.clear_prop(edit_cookie, &range, GUID_PROP_ATTRIBUTE)
+            .map_err(|e| Error::new(e.code(), "failed to clear display attribute"))?;
+
         match unsafe { composition.tf_composition.EndComposition(edit_cookie) } {
             Ok(()) => Ok(()),
             Err(e) if e.code() == E_UNEXPECTED => Ok(()),
@@ -86,24 +189,69 @@ impl EditSession {
 
     #[tracing::instrument(skip_all, ret, err)]
     fn update_composition(&self, edit_cookie: u32) -> Result<()> {
-        let composition = self.ime.composition().unwrap();
-
-        let converted = self
+        let Some((diff, is_empty, attribute_atom, reading, tf_composition)) = self
             .ime
-            .state()
-            .unwrap()
-            .transcriber
-            .convert(&composition.input);
-        tracing::trace!(composition.input, converted);
+            .with_state(|state| {
+                let composition = state.composition.as_ref()?;
 
-        let encoded = converted.encode_utf16().collect::<Vec<_>>();
+                let resolved = if composition.literal {
+                    Shortcode::Partial
+                } else {
+                    state.shortcodes.resolve(
+                        &state.settings.shortcode_prefix,
+                        &composition.input,
+                        utils::local_now,
+                    )
+                };
+                let (diff, attribute) = match resolved {
+                    Shortcode::None if let Some(candidates) = &composition.candidates => (
+                        state.preedit.set_text(candidates.selected()),
+                        display_attribute::DisplayAttribute::Converted,
+                    ),
+                    Shortcode::None => (
+                        state.preedit.convert(&composition.input, |input, output| {
+                            state.transcriber.convert_into(input, output)
+                        }),
+                        display_attribute::DisplayAttribute::Input,
+                    ),
+                    Shortcode::Symbol(symbol) => (
+                        state.preedit.set_text(&symbol),
+                        display_attribute::DisplayAttribute::Converted,
+                    ),
+                    Shortcode::Partial => (
+                        state.preedit.set_text(if composition.literal {
+                            &composition.typed
+                        } else {
+                            &composition.input
+                        }),
+                        display_attribute::DisplayAttribute::Input,
+                    ),
+                };
+                tracing::trace!(composition.input, converted = state.preedit.text());
 
-        let range = unsafe { composition.tf_composition.GetRange() }?;
+                Some((
+                    diff,
+                    state.preedit.text().is_empty(),
+                    state.display_attribute_atoms.get(attribute),
+                    composition.typed.clone(),
+                    composition.tf_composition.clone(),
+                ))
+            })
+            .flatten()
+        else {
+            return Ok(());
+        };
+
+        if !diff.is_empty() {
+            self.ime.with_preedit_utf16(|text| {
+                self.replace_changed_text(edit_cookie, &tf_composition, diff, text)
+            })?;
+            tracing::trace!(?diff, "set text");
+        }
 
-        unsafe { range.SetText(edit_cookie, 0, &encoded) }?;
-        tracing::trace!("set text");
+        let range = unsafe { tf_composition.GetRange() }?;
 
-        if !encoded.is_empty() {
+        if !is_empty {
             self.set_prop(
                 edit_cookie,
                 &range,
@@ -111,6 +259,18 @@ impl EditSession {
                 globals::IME_LANGID as i32,
             )?;
             tracing::trace!("set composition lang");
+
+            self.set_prop(
+                edit_cookie,
+                &range,
+                GUID_PROP_ATTRIBUTE,
+                attribute_atom as i32,
+            )?;
+            tracing::trace!("set composition display attribute");
+
+            // The text keeps how it was typed once committed, as long as it isn't replaced.
+            self.set_prop(edit_cookie, &range, GUID_PROP_READING, reading.as_str())?;
+            tracing::trace!("set composition reading");
         }
 
         // update the selection, we'll make it an insertion point just past the inserted text.
@@ -132,196 +292,778 @@ impl EditSession {
         Ok(())
     }
 
+    /// Replaces only the span of the composition text changed by `diff`, where `text` is the
+    /// whole new text, sparing editors from redrawing and recording the unchanged parts.
     // #[tracing::instrument(skip_all, ret, err)]
-    fn set_prop(&self, edit_cookie: u32, range: &ITfRange, prop: GUID, value: i32) -> Result<()> {
-        let language_prop = unsafe { self.ctx.GetProperty(&prop) }?;
-        let var = windows::Win32::System::Variant::VARIANT::from(value);
-        unsafe { language_prop.SetValue(edit_cookie, range, &var) }
-    }
-}
-
-#[implement(ITfEditSession)]
-#[derive(Debug)]
-struct AddSingleEditSession {
-    base: EditSession,
-    ch: u8,
-}
-
-impl ITfEditSession_Impl for AddSingleEditSession_Impl {
-    // #[tracing::instrument(skip_all, ret, err)]
-    fn DoEditSession(&self, edit_cookie: u32) -> Result<()> {
-        let ctx = &self.base.ctx;
+    fn replace_changed_text(
+        &self,
+        edit_cookie: u32,
+        composition: &ITfComposition,
+        diff: text::Utf16Diff,
+        text: &[u16],
+    ) -> Result<()> {
+        let changed = unsafe { composition.GetRange() }?;
+        let mut shifted = 0;
+        unsafe { changed.Collapse(edit_cookie, TF_ANCHOR_START) }?;
+        unsafe {
+            changed.ShiftEnd(
+                edit_cookie,
+                (diff.start + diff.old_len) as i32,
+                &mut shifted,
+                std::ptr::null(),
+            )
+        }?;
+        unsafe {
+            changed.ShiftStart(
+                edit_cookie,
+                diff.start as i32,
+                &mut shifted,
+                std::ptr::null(),
+            )
+        }?;
 
-        let input = std::str::from_utf8(std::slice::from_ref(&self.ch)).unwrap();
-        let converted = self.base.ime.state().unwrap().transcriber.convert(input);
-        tracing::trace!(input, converted);
+        unsafe { changed.SetText(edit_cookie, 0, &text[diff.start..][..diff.new_len]) }?;
 
-        let encoded = converted.encode_utf16().collect::<Vec<_>>();
+        // Text inserted at an edge of the composition isn't necessarily covered by it.
+        if diff.start == 0 {
+            unsafe { composition.ShiftStart(edit_cookie, &changed) }?;
+        }
+        if diff.start + diff.new_len == text.len() {
+            unsafe { composition.ShiftEnd(edit_cookie, &changed) }?;
+        }
 
-        let selection = utils::get_selection(edit_cookie, ctx, TF_DEFAULT_SELECTION)?;
-        let selection_range = selection.range.as_ref().unwrap();
+        Ok(())
+    }
 
-        unsafe { selection_range.SetText(edit_cookie, 0, &encoded) }?;
-        unsafe { selection_range.Collapse(edit_cookie, TF_ANCHOR_END) }?;
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn set_prop(
+        &self,
+        edit_cookie: u32,
+        range: &ITfRange,
+        prop: GUID,
+        value: impl Into<windows::Win32::System::Variant::VARIANT>,
+    ) -> Result<()> {
+        let prop = unsafe { self.ctx.GetProperty(&prop) }?;
+        let var = value.into();
+        unsafe { prop.SetValue(edit_cookie, range, &var) }
+    }
 
-        utils::set_selection(edit_cookie, ctx, selection)
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn clear_prop(&self, edit_cookie: u32, range: &ITfRange, prop: GUID) -> Result<()> {
+        let prop = unsafe { self.ctx.GetProperty(&prop) }?;
+        unsafe { prop.Clear(edit_cookie, range) }
     }
 }
 
 #[implement(ITfEditSession)]
 #[derive(Debug)]
-struct AppendEditSession {
+struct OpsEditSession {
     base: EditSession,
-    ch: u8,
 }
 
-impl ITfEditSession_Impl for AppendEditSession_Impl {
+impl ITfEditSession_Impl for OpsEditSession_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn DoEditSession(&self, edit_cookie: u32) -> Result<()> {
-        self.base.start_composition(edit_cookie)?;
-        let ch = self
-            .base
-            .ime
-            .state()
-            .unwrap()
-            .transcriber
-            .adapt_char(self.ch);
-        self.base.ime.composition_mut().unwrap().input.push(ch);
-        self.base.update_composition(edit_cookie)
+        self.base.ime.contain(|| {
+            self.base.ime.with_state(|s| s.is_own_edit = true);
+            let result = self.base.apply_pending_ops(edit_cookie);
+
+            // The failing operation is dropped, and the ones after it get another edit session.
+            if let Err(e) = &result
+                && let Some((client_id, false)) = self
+                    .base
+                    .ime
+                    .with_state(|s| (s.client_id, s.pending_ops.is_empty()))
+            {
+                tracing::warn!(%e, "failed to apply an operation, applying the rest later");
+                if let Err(e) = unsafe {
+                    self.base.ctx.RequestEditSession(
+                        client_id,
+                        self.as_interface(),
+                        TF_ES_ASYNC | TF_ES_READWRITE,
+                    )
+                }
+                .and_then(HRESULT::ok)
+                {
+                    tracing::warn!(%e, "failed to request an edit session");
+                }
+            }
+            result
+        })
     }
 }
 
+/// An edit of the word committed last, done only if it's still right before the caret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LastCommitEdit {
+    /// Composes the word again from its roman input.
+    Reopen,
+    /// Replaces the word with its roman input.
+    Revert,
+}
+
 #[implement(ITfEditSession)]
 #[derive(Debug)]
-struct PopCharEditSession {
+struct LastCommitEditSession {
     base: EditSession,
+    edit: LastCommitEdit,
+    done: Cell<bool>,
 }
 
-impl ITfEditSession_Impl for PopCharEditSession_Impl {
+impl ITfEditSession_Impl for LastCommitEditSession_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn DoEditSession(&self, edit_cookie: u32) -> Result<()> {
-        self.base.ime.composition_mut().unwrap().input.pop();
+        self.base.ime.contain(|| {
+            self.base.ime.with_state(|s| s.is_own_edit = true);
+            let done = match self.edit {
+                LastCommitEdit::Reopen => self.base.reopen_last_commit(edit_cookie)?,
+                LastCommitEdit::Revert => self.base.revert_last_commit(edit_cookie)?,
+            };
+            self.done.set(done);
+            Ok(())
+        })
+    }
+}
+
+impl EditSession {
+    /// The caret along with the text before it long enough to hold the last commit, unless
+    /// there is no commit or the selection isn't empty.
+    fn caret_and_left(&self, edit_cookie: u32) -> Result<Option<(utils::TfSelection, String)>> {
+        let Some(context_len) = self
+            .ime
+            .with_state(|s| s.recent_commits.context_len())
+            .flatten()
+        else {
+            return Ok(None);
+        };
+
+        let selection = utils::get_selection(edit_cookie, &self.ctx, TF_DEFAULT_SELECTION)?;
+        let caret = selection.range.as_ref().ok_or(E_UNEXPECTED)?;
+        if !unsafe { caret.IsEmpty(edit_cookie) }?.as_bool() {
+            return Ok(None);
+        }
+
+        let left = self.text_before(edit_cookie, caret, context_len)?;
+        Ok(Some((selection, left)))
+    }
+
+    /// Replaces the word committed last, if it's still right before the caret, with a
+    /// composition of its roman input.
+    #[tracing::instrument(skip_all, ret, err)]
+    fn reopen_last_commit(&self, edit_cookie: u32) -> Result<bool> {
+        let Some((selection, left)) = self.caret_and_left(edit_cookie)? else {
+            return Ok(false);
+        };
+        let Some((len, commit)) = self
+            .ime
+            .with_state(|s| {
+                let len = s.recent_commits.reopenable_len(&left)?;
+                Some((len, s.recent_commits.pop()?))
+            })
+            .flatten()
+        else {
+            return Ok(false);
+        };
+        tracing::trace!(?commit, len, "reopen");
+
+        let caret = selection.range.as_ref().ok_or(E_UNEXPECTED)?;
+        let range = self.range_before(edit_cookie, caret, len)?;
+        if !self.uses_direct_input() {
+            unsafe { range.SetText(edit_cookie, 0, &[]) }?;
+            utils::set_selection(
+                edit_cookie,
+                &self.ctx,
+                utils::TfSelection {
+                    range: Some(range.clone()),
+                    style: selection.style,
+                },
+            )?;
+            self.start_composition(edit_cookie)?;
+        }
+
+        // Without a composition, the text is typed over as it was before the commit, which
+        // is only short of what came after it, like the composition would be.
+        if self.uses_direct_input() {
+            let text = commit.text.encode_utf16().collect::<Vec<_>>();
+            unsafe { range.SetText(edit_cookie, 0, &text) }?;
+            unsafe { range.Collapse(edit_cookie, TF_ANCHOR_END) }?;
+            utils::set_selection(
+                edit_cookie,
+                &self.ctx,
+                utils::TfSelection {
+                    range: Some(range),
+                    style: selection.style,
+                },
+            )?;
+            self.ime.with_state(|s| {
+                let input = s
+                    .transcriber
+                    .adapt_input(&commit.roman, s.settings.escapes.as_ref());
+                s.direct_input.resume(&commit.roman, &input, &commit.text);
+            });
+            return Ok(true);
+        }
+
+        self.ime.with_state(|s| {
+            if let Some(composition) = s.composition.as_mut() {
+                composition.input = s
+                    .transcriber
+                    .adapt_input(&commit.roman, s.settings.escapes.as_ref());
+                composition.typed = commit.roman;
+            }
+        });
+        self.update_composition(edit_cookie)?;
+
+        Ok(true)
+    }
+
+    /// Replaces the word committed last, if it's still right before the caret, with its
+    /// roman input.
+    #[tracing::instrument(skip_all, ret, err)]
+    fn revert_last_commit(&self, edit_cookie: u32) -> Result<bool> {
+        let Some((selection, left)) = self.caret_and_left(edit_cookie)? else {
+            return Ok(false);
+        };
+        let Some(reverted) = self
+            .ime
+            .with_state(|s| s.recent_commits.revert(&left))
+            .flatten()
+        else {
+            return Ok(false);
+        };
+        tracing::trace!(?reverted, "revert");
+
+        let caret = selection.range.as_ref().ok_or(E_UNEXPECTED)?;
+        let range = self.range_before(edit_cookie, caret, reverted.len)?;
+        let text = reverted.text.encode_utf16().collect::<Vec<_>>();
+        unsafe { range.SetText(edit_cookie, 0, &text) }?;
+        unsafe { range.Collapse(edit_cookie, TF_ANCHOR_END) }?;
+        utils::set_selection(
+            edit_cookie,
+            &self.ctx,
+            utils::TfSelection {
+                range: Some(range),
+                style: selection.style,
+            },
+        )?;
+
+        Ok(true)
+    }
+
+    /// Applies the queued operations in order, updating the composition text only once for a
+    /// run of appended keys.
+    ///
+    /// An operation failing is dropped, leaving the ones after it queued.
+    #[tracing::instrument(skip_all, ret, err)]
+    fn apply_pending_ops(&self, edit_cookie: u32) -> Result<()> {
+        let mut is_input_changed = false;
+
+        ops::apply_queued(
+            || self.ime.with_state(|s| s.pending_ops.pop()).flatten(),
+            |op| -> Result<()> {
+                tracing::trace!(?op);
+
+                // A run of appended keys is converted once, before anything else is applied.
+                if is_input_changed && !matches!(op, EditOp::Append(_)) {
+                    self.update_composition(edit_cookie)?;
+                    is_input_changed = false;
+                }
+
+                match op {
+                    EditOp::Append(ch) => is_input_changed |= self.append_key(edit_cookie, ch)?,
+                    EditOp::Insert(ch) => self.insert_single(edit_cookie, ch)?,
+                    EditOp::Forward(key) => self.ime.forward_key(key)?,
+                    op if self.uses_direct_input() => self.apply_direct(edit_cookie, op)?,
+                    EditOp::Backspace => self.pop_char(edit_cookie)?,
+                    EditOp::Finish => self.terminate_composition(edit_cookie)?,
+                    EditOp::Revert => self.revert_composition(edit_cookie)?,
+                    EditOp::Cancel => self.cancel(edit_cookie)?,
+                    EditOp::NextCandidate => self.next_candidate(edit_cookie)?,
+                }
+                Ok(())
+            },
+        )?;
+
+        if is_input_changed {
+            self.update_composition(edit_cookie)?;
+        }
+
+        Ok(())
+    }
+
+    /// Appends a key to the composition input, starting a composition if there is none, and
+    /// returns whether the input changed, leaving its conversion to the caller.
+    // #[tracing::instrument(skip(self), ret, err)]
+    fn append_key(&self, edit_cookie: u32, ch: char) -> Result<bool> {
+        if !self.uses_direct_input() {
+            self.start_composition(edit_cookie)?;
+        }
+        // The application may have refused the composition.
+        if self.uses_direct_input() {
+            self.apply_direct(edit_cookie, EditOp::Append(ch))?;
+            return Ok(false);
+        }
+
+        Ok(self
+            .ime
+            .with_state(|s| {
+                let composition = s.composition.as_mut()?;
+                composition.typed.push(ch);
+                composition.candidates = None;
+                s.transcriber
+                    .push_adapted(&mut composition.input, ch, s.settings.escapes.as_ref());
+                Some(())
+            })
+            .flatten()
+            .is_some())
+    }
+
+    /// Applies `op` to the text typed straight into the document, replacing the text inserted
+    /// for the previous key as long as it's still right before the caret.
+    #[tracing::instrument(skip(self), ret, err)]
+    fn apply_direct(&self, edit_cookie: u32, op: EditOp) -> Result<()> {
+        let selection = utils::get_selection(edit_cookie, &self.ctx, TF_DEFAULT_SELECTION)?;
+        let caret = selection.range.as_ref().ok_or(E_UNEXPECTED)?;
+        let is_caret = unsafe { caret.IsEmpty(edit_cookie) }?.as_bool();
+        let context_len = self
+            .ime
+            .with_state(|s| s.direct_input.context_len())
+            .unwrap_or_default();
+        // Not every document lets the text be read, in which case it's taken to be in place.
+        let left = self
+            .text_before(edit_cookie, caret, context_len.max(2))
+            .ok();
 
-        self.base.update_composition(edit_cookie)?;
+        let mut reading = None;
+        let Some((delete, insert)) = self
+            .ime
+            .with_state(|s| {
+                if !is_caret
+                    || left
+                        .as_ref()
+                        .is_some_and(|left| !s.direct_input.is_in_place(left))
+                {
+                    s.direct_input.clear();
+                }
+
+                let convert =
+                    |input: &str, output: &mut String| s.transcriber.convert_into(input, output);
+                let replacement = match op {
+                    EditOp::Append(ch) => {
+                        let adapted = s.transcriber.adapt_key(
+                            s.direct_input.input(),
+                            ch,
+                            s.settings.escapes.as_ref(),
+                        );
+                        Some(s.direct_input.push(ch, adapted, convert))
+                    }
+                    EditOp::Backspace => s.direct_input.pop(convert),
+                    EditOp::Finish => {
+                        s.recent_commits
+                            .push(s.direct_input.typed(), s.direct_input.text());
+                        s.word_history.push_text(s.direct_input.text());
+                        if !s.direct_input.is_empty() {
+                            reading = Some((
+                                s.direct_input.typed().to_owned(),
+                                s.direct_input.context_len(),
+                            ));
+                        }
+                        s.direct_input.finish();
+                        return None;
+                    }
+                    EditOp::Revert => Some(s.direct_input.revert()),
+                    EditOp::Cancel => Some(s.direct_input.cancel()),
+                    EditOp::Insert(_) | EditOp::Forward(_) | EditOp::NextCandidate => None,
+                };
 
-        let is_input_empty = self.base.ime.composition().unwrap().input.is_empty();
+                let mut insert = std::mem::take(&mut s.text_buffer);
+                insert.clear();
+                let delete = match replacement {
+                    Some(replacement) => {
+                        insert.extend_from_slice(replacement.insert);
+                        replacement.delete
+                    }
+                    // The key was eaten for an input no longer in place, so it has to do what
+                    // it would have done, deleting the selection or the character before.
+                    None if op == EditOp::Backspace && is_caret => left
+                        .as_ref()
+                        .and_then(|left| left.chars().next_back())
+                        .map_or(1, char::len_utf16),
+                    None if op == EditOp::Backspace => 0,
+                    None => {
+                        s.text_buffer = insert;
+                        return None;
+                    }
+                };
+                Some((delete, insert))
+            })
+            .flatten()
+        else {
+            // Committed text keeps how it was typed, like it would with a composition.
+            if let Some((reading, len)) = reading {
+                let range = self.range_before(edit_cookie, caret, len)?;
+                self.set_prop(
+                    edit_cookie,
+                    &range,
+                    GUID_PROP_LANGID,
+                    globals::IME_LANGID as i32,
+                )?;
+                self.set_prop(edit_cookie, &range, GUID_PROP_READING, reading.as_str())?;
+            }
+            return Ok(());
+        };
+
+        let result = if is_caret && delete == 0 && insert.is_empty() {
+            Ok(())
+        } else {
+            self.replace_before_caret(edit_cookie, selection, delete, &insert)
+        };
+        self.ime.with_state(|s| s.text_buffer = insert);
+        result
+    }
+
+    /// Replaces `delete` UTF-16 units before the caret, or the selection if it isn't empty,
+    /// with `text`, leaving the caret after it.
+    fn replace_before_caret(
+        &self,
+        edit_cookie: u32,
+        selection: utils::TfSelection,
+        delete: usize,
+        text: &[u16],
+    ) -> Result<()> {
+        let caret = selection.range.as_ref().ok_or(E_UNEXPECTED)?;
+        let range = if delete == 0 {
+            unsafe { caret.Clone() }?
+        } else {
+            self.range_before(edit_cookie, caret, delete)?
+        };
+        unsafe { range.SetText(edit_cookie, 0, text) }?;
+        unsafe { range.Collapse(edit_cookie, TF_ANCHOR_END) }?;
+        utils::set_selection(
+            edit_cookie,
+            &self.ctx,
+            utils::TfSelection {
+                range: Some(range),
+                style: selection.style,
+            },
+        )
+    }
+
+    fn insert_single(&self, edit_cookie: u32, ch: char) -> Result<()> {
+        let mut buffer = [0; 4];
+        let input = &*ch.encode_utf8(&mut buffer);
+        self.ime.with_state(|state| {
+            state.preedit.clear();
+            state.preedit.convert(input, |input, output| {
+                state.transcriber.convert_into(input, output)
+            });
+            tracing::trace!(input, converted = state.preedit.text());
+        });
+
+        let selection = utils::get_selection(edit_cookie, &self.ctx, TF_DEFAULT_SELECTION)?;
+        let selection_range = selection.range.as_ref().ok_or(E_UNEXPECTED)?;
+
+        self.ime
+            .with_preedit_utf16(|text| unsafe { selection_range.SetText(edit_cookie, 0, text) })?;
+        unsafe { selection_range.Collapse(edit_cookie, TF_ANCHOR_END) }?;
+        self.ime
+            .with_state(|s| s.word_history.push_text(s.preedit.text()));
+
+        utils::set_selection(edit_cookie, &self.ctx, selection)
+    }
+
+    fn pop_char(&self, edit_cookie: u32) -> Result<()> {
+        let Some(is_input_empty) = self
+            .ime
+            .with_state(|s| {
+                let composition = s.composition.as_mut()?;
+                composition.typed.pop();
+                composition.input.pop();
+                composition.candidates = None;
+                Some(composition.input.is_empty())
+            })
+            .flatten()
+        else {
+            return Ok(());
+        };
+
+        self.update_composition(edit_cookie)?;
         if is_input_empty {
-            self.base.terminate_composition(edit_cookie)?;
+            self.terminate_composition(edit_cookie)?;
         }
 
         Ok(())
     }
-}
 
-#[implement(ITfEditSession)]
-#[derive(Debug)]
-struct FinishEditSession {
-    base: EditSession,
-}
+    /// Commits the composition input as typed.
+    fn revert_composition(&self, edit_cookie: u32) -> Result<()> {
+        let is_composing = self.ime.with_state(|s| {
+            s.composition
+                .as_mut()
+                .map(|composition| composition.literal = true)
+                .is_some()
+        });
+        if is_composing != Some(true) {
+            return Ok(());
+        }
 
-impl ITfEditSession_Impl for FinishEditSession_Impl {
-    // #[tracing::instrument(skip_all, ret, err)]
-    fn DoEditSession(&self, edit_cookie: u32) -> Result<()> {
-        self.base.terminate_composition(edit_cookie)
+        self.update_composition(edit_cookie)?;
+        self.terminate_composition(edit_cookie)
     }
-}
 
-#[implement(ITfEditSession)]
-#[derive(Debug)]
-struct CancelEditSession {
-    base: EditSession,
-}
+    /// Shows the next candidate for the converted text of the composition in its place.
+    fn next_candidate(&self, edit_cookie: u32) -> Result<()> {
+        let is_switched = self.ime.with_state(|s| {
+            if s.composition.as_ref()?.literal {
+                return None;
+            }
+            let candidates = match s.composition.as_mut()?.candidates.take() {
+                Some(candidates) => candidates,
+                None => s.phrase_candidates()?,
+            };
+            let composition = s.composition.as_mut()?;
+            composition.candidates.insert(candidates).select_next();
+            Some(())
+        });
+        if is_switched.flatten().is_none() {
+            return Ok(());
+        }
 
-impl ITfEditSession_Impl for CancelEditSession_Impl {
-    // #[tracing::instrument(skip_all, ret, err)]
-    fn DoEditSession(&self, edit_cookie: u32) -> Result<()> {
-        self.base.ime.composition_mut().unwrap().input.clear();
-        self.base.update_composition(edit_cookie)?;
-        self.base.terminate_composition(edit_cookie)
+        self.update_composition(edit_cookie)
+    }
+
+    fn cancel(&self, edit_cookie: u32) -> Result<()> {
+        let is_composing = self.ime.with_state(|s| {
+            let Some(composition) = s.composition.as_mut() else {
+                return false;
+            };
+            composition.typed.clear();
+            composition.input.clear();
+            true
+        });
+        if is_composing != Some(true) {
+            return Ok(());
+        }
+
+        self.update_composition(edit_cookie)?;
+        self.terminate_composition(edit_cookie)
     }
 }
 
 impl Ime_Impl {
-    fn request_edit_session(
+    /// Queues `ops` and applies them, along with anything queued before, in one edit session.
+    ///
+    /// When the application refuses a synchronous lock, the operations stay queued for an
+    /// asynchronous edit session, while later ones keep queueing behind them.
+    #[tracing::instrument(skip(self, ctx), ret, err)]
+    pub(crate) fn apply_ops(
         &self,
         ctx: &ITfContext,
-        edit_session: impl ComObjectInner<Outer: ComObjectInterface<ITfEditSession>>,
+        ops: impl IntoIterator<Item = EditOp> + std::fmt::Debug,
     ) -> Result<()> {
-        let edit_session = edit_session.into_object();
-        unsafe {
+        let Some(client_id) = self.with_state(|s| {
+            s.pending_ops.extend(ops);
+            s.client_id
+        }) else {
+            return Ok(());
+        };
+
+        let edit_session = OpsEditSession {
+            base: EditSession::new(self, ctx),
+        }
+        .into_object();
+        let response = ops::request_session(|mode| {
+            let flags = match mode {
+                LockMode::Sync => TF_ES_SYNC,
+                LockMode::Async => TF_ES_ASYNC,
+            };
+            let result = unsafe {
+                ctx.RequestEditSession(
+                    client_id,
+                    edit_session.as_interface(),
+                    flags | TF_ES_READWRITE,
+                )
+            }?;
+
+            match result {
+                TF_E_SYNCHRONOUS => Ok(LockResponse::SyncRefused),
+                TF_S_ASYNC => Ok(LockResponse::Scheduled),
+                result => result.ok().map(|_| LockResponse::Done),
+            }
+        })?;
+        tracing::trace!(?response);
+
+        Ok(())
+    }
+
+    /// Forgets the recent commits unless the word committed last is still right before the
+    /// caret, after something other than the IME changed the selection.
+    pub(crate) fn caret_moved(&self, ctx: &ITfContext, edit_cookie: u32) -> Result<()> {
+        let left = EditSession::new(self, ctx).caret_and_left(edit_cookie)?;
+        self.with_state(|s| {
+            match left {
+                Some((_, left)) => s.recent_commits.caret_moved(&left),
+                None => s.recent_commits.clear(),
+            }
+            // The words before the caret are no longer the ones committed last.
+            if s.recent_commits.is_empty() {
+                s.word_history.clear();
+            }
+        });
+        Ok(())
+    }
+
+    /// Composes the word committed last again, returning whether it was right before the
+    /// caret to do so.
+    pub(crate) fn reopen_last_commit(&self, ctx: &ITfContext) -> Result<bool> {
+        self.edit_last_commit(ctx, LastCommitEdit::Reopen)
+    }
+
+    /// Turns the composition, or else the word committed last if it's right before the
+    /// caret, back into the roman input it was typed as.
+    ///
+    /// Returns whether there was anything to revert.
+    #[tracing::instrument(skip(self, ctx), ret, err)]
+    pub(crate) fn revert(&self, ctx: &ITfContext) -> Result<bool> {
+        if self.is_composing() {
+            self.apply_ops(ctx, [EditOp::Revert])?;
+            return Ok(true);
+        }
+
+        self.edit_last_commit(ctx, LastCommitEdit::Revert)
+    }
+
+    /// Does `edit` on the word committed last, returning whether it was right before the
+    /// caret to do so.
+    ///
+    /// Nothing is done unless the document can be locked right away, as the key would be
+    /// forwarded otherwise.
+    #[tracing::instrument(skip(self, ctx), ret, err)]
+    fn edit_last_commit(&self, ctx: &ITfContext, edit: LastCommitEdit) -> Result<bool> {
+        let Some(client_id) = self
+            .with_state(|s| s.pending_ops.is_empty().then_some(s.client_id))
+            .flatten()
+        else {
+            return Ok(false);
+        };
+
+        let edit_session = LastCommitEditSession {
+            base: EditSession::new(self, ctx),
+            edit,
+            done: Cell::new(false),
+        }
+        .into_object();
+        let result = unsafe {
             ctx.RequestEditSession(
-                self.state().map_or(0, |s| s.client_id),
+                client_id,
                 edit_session.as_interface(),
                 TF_ES_SYNC | TF_ES_READWRITE,
             )
+        }?;
+        if result == TF_E_SYNCHRONOUS {
+            return Ok(false);
         }
-        .map(|_| ())
+        result.ok()?;
+
+        Ok(edit_session.done.get())
     }
 
-    #[tracing::instrument(skip_all, ret, err)]
-    pub(crate) fn add_single_char(&self, ctx: &ITfContext, ch: u8) -> Result<()> {
-        self.request_edit_session(
-            ctx,
-            AddSingleEditSession {
-                base: EditSession::new(self, ctx),
-                ch,
-            },
-        )
+    /// Queues `op` behind operations still waiting for an edit session, if there are any.
+    fn queue_behind_pending(&self, op: EditOp) -> bool {
+        self.with_state(|s| s.pending_ops.push_behind(op))
+            .unwrap_or(false)
     }
 
-    #[tracing::instrument(skip_all, ret, err)]
-    pub(crate) fn append_char_to_composition(&self, ctx: &ITfContext, ch: u8) -> Result<()> {
-        self.request_edit_session(
-            ctx,
-            AppendEditSession {
-                base: EditSession::new(self, ctx),
-                ch,
-            },
-        )
+    fn composition_ctx(&self) -> Option<ITfContext> {
+        self.with_state(|s| s.composition.as_ref().map(|c| c.ctx.clone()))
+            .flatten()
     }
 
-    #[tracing::instrument(skip_all, ret, err)]
-    pub(crate) fn pop_char_from_composition(&self, ctx: &ITfContext) -> Result<()> {
-        self.request_edit_session(
-            ctx,
-            PopCharEditSession {
-                base: EditSession::new(self, ctx),
-            },
-        )
+    /// Runs `f` on a copy of the UTF-16 preedit text, so that `f` can set it in the document
+    /// without holding the state. The copy reuses a buffer kept in the state.
+    ///
+    /// Fails with `E_UNEXPECTED` if the state is busy, rather than handing `f` an empty text.
+    fn with_preedit_utf16<R>(&self, f: impl FnOnce(&[u16]) -> Result<R>) -> Result<R> {
+        self.state
+            .with_lent(
+                |s| {
+                    let mut buffer = std::mem::take(&mut s.text_buffer);
+                    buffer.clear();
+                    buffer.extend_from_slice(s.preedit.utf16());
+                    buffer
+                },
+                |buffer| f(buffer),
+                |s, buffer| s.text_buffer = buffer,
+            )
+            .ok_or(E_UNEXPECTED)?
+    }
+
+    /// Whether there is a composition, or will be once the queued operations are applied.
+    ///
+    /// Keys typed without a composition count as one until they are committed.
+    pub(crate) fn is_composing(&self) -> bool {
+        self.with_state(|s| {
+            let input_len = s
+                .composition_input()
+                .map_or(0, |input| input.chars().count());
+            s.pending_ops.projected_input_len(input_len) > 0
+        })
+        .unwrap_or(false)
     }
 
     #[tracing::instrument(skip_all, ret, err)]
     pub(crate) fn finish_composition(&self, ctx: Option<&ITfContext>) -> Result<()> {
-        let Some(ctx) = ctx
-            .cloned()
-            .or_else(|| self.composition().map(|c| c.ctx.clone()))
-        else {
+        let Some(ctx) = ctx.cloned().or_else(|| self.composition_ctx()) else {
+            // A composition may be waiting for an edit session to start it.
+            self.queue_behind_pending(EditOp::Finish);
             return Ok(());
         };
 
-        self.request_edit_session(
-            &ctx,
-            FinishEditSession {
-                base: EditSession::new(self, &ctx),
-            },
-        )
+        self.apply_ops(&ctx, [EditOp::Finish])
     }
 
-    #[tracing::instrument(skip_all, ret, err)]
-    pub(crate) fn cancel_composition(&self, ctx: &ITfContext) -> Result<()> {
-        self.request_edit_session(
-            ctx,
-            CancelEditSession {
-                base: EditSession::new(self, ctx),
-            },
-        )
+    /// Commits or cancels the composition as the policy says for `terminator`, along with the
+    /// virtual-key code of the terminating `key`, if there is one.
+    ///
+    /// Returns whether the terminating key is eaten.
+    #[tracing::instrument(skip(self, ctx), ret, err)]
+    pub(crate) fn end_composition(
+        &self,
+        ctx: Option<&ITfContext>,
+        terminator: Terminator,
+        key: Option<u16>,
+    ) -> Result<bool> {
+        let action = self
+            .with_state(|s| s.settings.commit_policy.action(terminator))
+            .unwrap_or(CommitAction::Commit);
+        let op = if action.commits() {
+            EditOp::Finish
+        } else {
+            EditOp::Cancel
+        };
+
+        match ctx.cloned().or_else(|| self.composition_ctx()) {
+            Some(ctx) => self.apply_ops(&ctx, [op])?,
+            // A composition may be waiting for an edit session to start it.
+            None if self.queue_behind_pending(op) => {}
+            None => return Ok(false),
+        }
+        if !action.forwards_key() {
+            return Ok(true);
+        }
+
+        // While the commit waits for an edit session, the key is held back behind it, rather
+        // than reaching the application before the committed text.
+        Ok(key.is_some_and(|key| self.queue_behind_pending(EditOp::Forward(key))))
     }
 }
 
 impl ITfCompositionSink_Impl for Ime_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnCompositionTerminated(&self, _: u32, _: Ref<'_, ITfComposition>) -> Result<()> {
-        tracing::trace!("composition termination");
-        self.finish_composition(None)
+        self.contain(|| {
+            tracing::trace!("composition termination");
+            self.finish_composition(None)
+        })
     }
 }
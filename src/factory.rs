@@ -3,7 +3,7 @@
 
 use std::ffi::c_void;
 use std::sync::atomic::{AtomicI32, Ordering};
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
 
 use windows::Win32::System::Com::*;
 
@@ -37,36 +37,51 @@ impl IClassFactory_Impl for ClassFactory_Impl {
         iid: *const GUID,
         object: *mut *mut c_void,
     ) -> Result<()> {
-        if object.is_null() {
-            return E_INVALIDARG.ok();
-        }
-
-        if outer.is_some() {
-            return CLASS_E_NOAGGREGATION.ok();
-        }
-
-        let instance = Ime::new()?.into_object();
-        unsafe { instance.QueryInterface(iid, object) }.ok()
+        panic_guard::contain(|| {
+            if object.is_null() {
+                return E_INVALIDARG.ok();
+            }
+
+            if outer.is_some() {
+                return CLASS_E_NOAGGREGATION.ok();
+            }
+
+            let instance = Ime::new()?.into_object();
+            unsafe { instance.QueryInterface(iid, object) }.ok()
+        })
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn LockServer(&self, lock: BOOL) -> Result<()> {
-        if lock.as_bool() {
-            dll_add_ref();
-        } else {
-            dll_release();
-        }
-        Ok(())
+        panic_guard::contain(|| {
+            if lock.as_bool() {
+                dll_add_ref();
+            } else {
+                dll_release();
+            }
+            Ok(())
+        })
     }
 }
 
+/// Locks the class factory, which stays usable even if a panic poisoned the lock.
+fn class_factory_object() -> MutexGuard<'static, OnceLock<StaticComObject<ClassFactory>>> {
+    CLASS_FACTORY_OBJECT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
 fn free_global_objects() {
-    CLASS_FACTORY_OBJECT.lock().unwrap().take();
+    class_factory_object().take();
 }
 
 #[unsafe(no_mangle)]
 #[expect(nonstandard_style, reason = "DLL export")]
 fn DllGetClassObject(clsid: *const GUID, iid: *const GUID, object: *mut *mut c_void) -> HRESULT {
+    panic_guard::contain_hresult(|| get_class_object(clsid, iid, object))
+}
+
+fn get_class_object(clsid: *const GUID, iid: *const GUID, object: *mut *mut c_void) -> HRESULT {
     let Some(clsid) = (unsafe { clsid.as_ref() }) else {
         return E_INVALIDARG;
     };
@@ -75,7 +90,7 @@ fn DllGetClassObject(clsid: *const GUID, iid: *const GUID, object: *mut *mut c_v
     };
 
     if (*iid == IClassFactory::IID || *iid == IUnknown::IID) && *clsid == IME_CLSID {
-        let lock = CLASS_FACTORY_OBJECT.lock().unwrap();
+        let lock = class_factory_object();
         let factory = lock.get_or_init(|| ClassFactory.into_static());
         let factory = factory.as_interface::<IClassFactory>().to_owned();
         unsafe { object.write(factory.into_raw()) };
@@ -90,9 +105,11 @@ fn DllGetClassObject(clsid: *const GUID, iid: *const GUID, object: *mut *mut c_v
 #[unsafe(no_mangle)]
 #[expect(nonstandard_style, reason = "DLL export")]
 fn DllCanUnloadNow() -> HRESULT {
-    if DLL_REF_COUNT.load(Ordering::Relaxed) < 0 {
-        S_OK
-    } else {
-        S_FALSE
-    }
+    panic_guard::contain_hresult(|| {
+        if DLL_REF_COUNT.load(Ordering::Relaxed) < 0 {
+            S_OK
+        } else {
+            S_FALSE
+        }
+    })
 }
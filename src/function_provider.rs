@@ -6,34 +6,37 @@ use crate::*;
 impl ITfFunctionProvider_Impl for Ime_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn GetType(&self) -> Result<GUID> {
-        Ok(globals::IME_CLSID)
+        self.contain(|| Ok(globals::IME_CLSID))
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn GetDescription(&self) -> Result<BSTR> {
-        Err(E_NOTIMPL.into())
+        self.contain(|| Err(E_NOTIMPL.into()))
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn GetFunction(&self, guid: *const GUID, iid: *const GUID) -> Result<IUnknown> {
-        let guid = unsafe { guid.as_ref() }.unwrap();
-        let iid = unsafe { iid.as_ref() }.unwrap();
+        self.contain(|| {
+            let guid = unsafe { guid.as_ref() }.ok_or(E_INVALIDARG)?;
+            let iid = unsafe { iid.as_ref() }.ok_or(E_INVALIDARG)?;
 
-        if guid == &GUID::zeroed() {
-            let mut object = std::ptr::null_mut();
-            unsafe { self.QueryInterface(iid, &mut object) }
-                .ok()
-                .map(|_| unsafe { IUnknown::from_raw(object) })
-        } else {
-            Err(E_NOINTERFACE.into())
-        }
+            // Functions are asked for by the null GUID, or by the type of the provider.
+            if guid == &GUID::zeroed() || guid == &globals::IME_CLSID {
+                let mut object = std::ptr::null_mut();
+                unsafe { self.QueryInterface(iid, &mut object) }
+                    .ok()
+                    .map(|_| unsafe { IUnknown::from_raw(object) })
+            } else {
+                Err(E_NOINTERFACE.into())
+            }
+        })
     }
 }
 
 impl ITfFunction_Impl for Ime_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn GetDisplayName(&self) -> Result<BSTR> {
-        Err(E_NOTIMPL.into())
+        self.contain(|| Err(E_NOTIMPL.into()))
     }
 }
 
@@ -44,14 +47,16 @@ impl ITfFnGetPreferredTouchKeyboardLayout_Impl for Ime_Impl {
         layout_type: *mut TKBLayoutType,
         preferred_layout_id: *const u16,
     ) -> Result<()> {
-        if !layout_type.is_null() && !preferred_layout_id.is_null() {
-            unsafe {
-                layout_type.write(TKBLT_OPTIMIZED);
-                (preferred_layout_id as *mut u16).write(TKBL_UNDEFINED as _);
+        self.contain(|| {
+            if !layout_type.is_null() && !preferred_layout_id.is_null() {
+                unsafe {
+                    layout_type.write(TKBLT_OPTIMIZED);
+                    (preferred_layout_id as *mut u16).write(TKBL_UNDEFINED as _);
+                }
+                Ok(())
+            } else {
+                E_INVALIDARG.ok()
             }
-            Ok(())
-        } else {
-            E_INVALIDARG.ok()
-        }
+        })
     }
 }
@@ -16,3 +16,11 @@ pub(crate) const IME_ICON_INDEX_AVRO: u32 = (-11i32).cast_unsigned();
 pub(crate) const IME_PROFILE_KHIPRO: GUID = GUID::from_u128(0x5f9083f2_0f4a_4c6e_af95_12c7bfc1603e);
 pub(crate) const IME_PROFILE_DESCRIPTION_KHIPRO: &str = "Ũõ Keyboard (ক্ষিপ্র)";
 pub(crate) const IME_ICON_INDEX_KHIPRO: u32 = (-12i32).cast_unsigned();
+
+pub(crate) const IME_DESCRIPTION_ENGLISH: &str = "Ũõ Keyboard (English)";
+pub(crate) const IME_ICON_INDEX_ENGLISH: u32 = (-13i32).cast_unsigned();
+
+pub(crate) const IME_DISPLAY_ATTRIBUTE_INPUT: GUID =
+    GUID::from_u128(0xc2f13413_04b9_4232_9e6c_e269385083af);
+pub(crate) const IME_DISPLAY_ATTRIBUTE_CONVERTED: GUID =
+    GUID::from_u128(0x0afc5611_1b1a_4c1b_a23a_e2aa0eeb6d11);
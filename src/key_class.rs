@@ -1,6 +1,7 @@
 // Copyright 2026 Muhammad Ragib Hasin
 // SPDX-License-Identifier: MPL-2.0
 
+use uo_keyboard_core::{commit::Terminator, hotkey::Hotkey};
 use windows::Win32::UI::Input::KeyboardAndMouse::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,7 +9,7 @@ pub(crate) enum KeyClass {
     Letter,
     Symbol,
     NumPad,
-    Terminator,
+    Terminator(Terminator),
     Backspace,
     Decimal,
     Function,
@@ -26,8 +27,14 @@ pub(crate) enum KeyAction {
     Pass,
     OneShot,
     Append,
-    End,
+    End(Terminator),
     Backspace,
+    /// Composes the word committed last again, if it's right before the caret.
+    Reopen,
+    /// Turns the composition, or the word committed last, back into its roman input.
+    Revert,
+    /// Switches the composition to the next of its candidates.
+    NextCandidate,
     Cancel,
     AppendDot,
 }
@@ -44,10 +51,11 @@ const SYMBOL_KEYS: &[VIRTUAL_KEY] = &[
     VK_OEM_PLUS,
     VK_OEM_COMMA,
     VK_OEM_PERIOD,
+    VK_OEM_8,
+    VK_OEM_102,
 ];
-const TERMINATOR_KEYS: &[VIRTUAL_KEY] = &[
-    VK_TAB, VK_SPACE, VK_RETURN, VK_LEFT, VK_RIGHT, VK_UP, VK_DOWN, VK_HOME, VK_END, VK_PRIOR,
-    VK_NEXT,
+const NAVIGATION_KEYS: &[VIRTUAL_KEY] = &[
+    VK_LEFT, VK_RIGHT, VK_UP, VK_DOWN, VK_HOME, VK_END, VK_PRIOR, VK_NEXT,
 ];
 
 impl KeyClass {
@@ -62,8 +70,8 @@ impl KeyClass {
             Self::Symbol
         } else if is_key_in_range(key, VK_A, VK_Z) {
             Self::Letter
-        } else if matches_key(key, TERMINATOR_KEYS) {
-            Self::Terminator
+        } else if let Some(terminator) = terminator(key) {
+            Self::Terminator(terminator)
         } else {
             Self::Function
         }
@@ -112,20 +120,48 @@ impl KeyAction {
             (Decimal, None, true) => Self::AppendDot,
             (Decimal, _, _) => Self::Pass,
 
-            (Backspace, _, false) => Self::Pass,
+            (Backspace, None, false) => Self::Reopen,
+            (Backspace, Shift | Other, false) => Self::Pass,
             (Backspace, None, true) => Self::Backspace,
             (Backspace, Shift | Other, true) => Self::Cancel,
 
-            (Terminator, _, false) => Self::Pass,
-            (Terminator, _, true) => Self::End,
+            (Terminator(_), _, false) => Self::Pass,
+            (Terminator(terminator), _, true) => Self::End(terminator),
         }
     }
 }
 
+/// Whether the key is on the navigation cluster, which is told apart from the numeric keypad
+/// by the extended key flag.
+pub(crate) fn is_extended(key: u16) -> bool {
+    matches_key(key, NAVIGATION_KEYS)
+}
+
 fn is_key_in_range(key: u16, start: VIRTUAL_KEY, end: VIRTUAL_KEY) -> bool {
     (start.0..=end.0).contains(&key)
 }
 
+fn terminator(key: u16) -> Option<Terminator> {
+    match VIRTUAL_KEY(key) {
+        VK_SPACE => Some(Terminator::Space),
+        VK_TAB => Some(Terminator::Tab),
+        VK_RETURN => Some(Terminator::Enter),
+        VK_ESCAPE => Some(Terminator::Escape),
+        _ if matches_key(key, NAVIGATION_KEYS) => Some(Terminator::Navigation),
+        _ => None,
+    }
+}
+
+/// Whether `key`, along with the modifiers held now, is `hotkey`.
+pub(crate) fn is_hotkey(hotkey: &Hotkey, key: u16) -> bool {
+    hotkey.matches(
+        key,
+        is_active(VK_CONTROL),
+        is_active(VK_MENU),
+        is_active(VK_SHIFT),
+    )
+}
+
 pub(crate) fn is_active(key: VIRTUAL_KEY) -> bool {
     (unsafe { GetAsyncKeyState(key.0 as _) } & i16::MIN == i16::MIN)
 }
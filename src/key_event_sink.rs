@@ -3,42 +3,135 @@
 
 use crate::*;
 
-use key_class::{KeyAction, is_active};
-use windows::Win32::UI::{Input::KeyboardAndMouse::*, WindowsAndMessaging::GetMessageExtraInfo};
-
-const SYNTH: usize = 0x746e7953;
+use key_class::KeyAction;
+use uo_keyboard_core::{keys::KeyInput, ops::EditOp};
+use windows::Win32::UI::Input::KeyboardAndMouse::*;
 
 impl Ime_Impl {
     // This theoretically makes sense, but a confirmation is needed whether a real
     // world situation exists where this becomes true.
     // #[tracing::instrument(skip_all, ret)]
     fn is_keyboard_disabled(&self) -> bool {
-        let state = self.state().unwrap();
+        let Some(thread_mgr) = self.with_state(|s| s.thread_mgr.clone()) else {
+            return true;
+        };
+
+        unsafe { thread_mgr.GetFocus() }.is_ok_and(|m| unsafe { m.GetTop() }.is_err())
+            && compartment::read_bool(&thread_mgr, GUID_COMPARTMENT_KEYBOARD_DISABLED)
+            && compartment::read_bool(&thread_mgr, GUID_COMPARTMENT_EMPTYCONTEXT)
+    }
+
+    /// Whether `ch` starts the shortcode prefix or the opening escape delimiter.
+    // #[tracing::instrument(skip_all, ret)]
+    fn starts_composition(&self, ch: char) -> bool {
+        self.with_state(|s| {
+            s.settings.shortcode_prefix.starts_with(ch)
+                || s.settings
+                    .escapes
+                    .as_ref()
+                    .is_some_and(|escapes| escapes.open().starts_with(ch))
+        })
+        .unwrap_or(false)
+    }
+
+    // #[tracing::instrument(skip_all, ret)]
+    fn key_action(&self, key: u16) -> KeyAction {
+        let composing = self.is_composing();
+
+        if composing
+            && self
+                .with_state(|s| {
+                    s.settings
+                        .candidate_key
+                        .is_some_and(|hotkey| key_class::is_hotkey(&hotkey, key))
+                })
+                .unwrap_or(false)
+        {
+            return KeyAction::NextCandidate;
+        }
+
+        // A space types a waiting dead key by itself, and is kept within a literal segment or
+        // a phrase.
+        if key == VK_SPACE.0
+            && self
+                .with_state(|s| s.dead_keys.pending().is_some() || s.keeps_space())
+                .unwrap_or(false)
+        {
+            return if composing {
+                KeyAction::Append
+            } else {
+                KeyAction::OneShot
+            };
+        }
+
+        if self
+            .with_state(|s| {
+                let is_revert_key = s
+                    .settings
+                    .revert_key
+                    .is_some_and(|hotkey| key_class::is_hotkey(&hotkey, key));
+                is_revert_key && (composing || !s.recent_commits.is_empty())
+            })
+            .unwrap_or(false)
+        {
+            return KeyAction::Revert;
+        }
+
+        match KeyAction::classify(key, composing) {
+            KeyAction::Reopen
+                if self
+                    .with_state(|s| s.recent_commits.is_empty())
+                    .unwrap_or(true) =>
+            {
+                KeyAction::Pass
+            }
+            action => action,
+        }
+    }
+
+    /// The keyboard is closed while in English mode, passing every key through.
+    // #[tracing::instrument(skip_all, ret)]
+    fn is_keyboard_open(&self) -> bool {
+        self.input_mode()
+            .is_some_and(|mode| mode != lang_bar_item::InputMode::English)
+    }
+}
 
-        unsafe { state.thread_mgr.GetFocus() }.is_ok_and(|m| unsafe { m.GetTop() }.is_err())
-            && compartment::read_bool(&state.thread_mgr, GUID_COMPARTMENT_KEYBOARD_DISABLED)
-            && compartment::read_bool(&state.thread_mgr, GUID_COMPARTMENT_EMPTYCONTEXT)
+impl ActiveImeState {
+    /// Whether a space is typed into the composition, rather than ending it.
+    fn keeps_space(&self) -> bool {
+        let Some(input) = self.composition_input() else {
+            return false;
+        };
+        self.settings
+            .escapes
+            .as_ref()
+            .is_some_and(|escapes| escapes.is_open(input))
+            || self.settings.composition_mode.keeps_space(input)
     }
 }
 
 impl ITfKeyEventSink_Impl for Ime_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnSetFocus(&self, _: BOOL) -> Result<()> {
-        Ok(())
+        self.contain(|| Ok(()))
     }
 
     #[tracing::instrument(skip_all, ret, err)]
     fn OnTestKeyDown(&self, _: Ref<'_, ITfContext>, wparam: WPARAM, _: LPARAM) -> Result<BOOL> {
-        Ok((!self.is_keyboard_disabled()
-            && unsafe { GetMessageExtraInfo() } != LPARAM(SYNTH.cast_signed())
-            && !matches!(
-                (
-                    KeyAction::classify(wparam.0 as _, self.composition().is_some()),
-                    convert_vkey(wparam.0 as _),
-                ),
-                (KeyAction::Pass, _) | (KeyAction::OneShot | KeyAction::Append, Err(_))
-            ))
-        .into())
+        self.contain(|| {
+            Ok((!self.is_forwarded(wparam.0 as _)
+                && self.is_keyboard_open()
+                && !self.is_keyboard_disabled()
+                && match self.key_action(wparam.0 as _) {
+                    KeyAction::Pass => false,
+                    KeyAction::OneShot | KeyAction::Append => {
+                        read_key(wparam.0 as _, false).is_ok_and(|input| input != KeyInput::None)
+                    }
+                    _ => true,
+                })
+            .into())
+        })
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
@@ -48,64 +141,138 @@ impl ITfKeyEventSink_Impl for Ime_Impl {
         wparam: WPARAM,
         lparam: LPARAM,
     ) -> Result<BOOL> {
-        self.OnKeyUp(ctx, wparam, lparam)
+        self.contain(|| self.OnKeyUp(ctx, wparam, lparam))
     }
 
     #[tracing::instrument(skip(self, ctx), ret, err)]
     fn OnKeyDown(&self, ctx: Ref<'_, ITfContext>, wparam: WPARAM, lparam: LPARAM) -> Result<BOOL> {
-        let composing = self.composition().is_some();
-        let is_eaten = !self.is_keyboard_disabled()
-            && unsafe { GetMessageExtraInfo() } != LPARAM(SYNTH.cast_signed())
-            && match (
-                KeyAction::classify(wparam.0 as _, composing),
-                convert_vkey(wparam.0 as _),
-            ) {
-                (KeyAction::Pass, _) | (KeyAction::OneShot | KeyAction::Append, Err(_)) => false,
-                (KeyAction::OneShot, Ok(ch)) => {
-                    self.add_single_char(ctx.unwrap(), ch)?;
-                    true
-                }
-                (KeyAction::Append, Ok(ch)) => {
-                    self.append_char_to_composition(ctx.unwrap(), ch)?;
-                    true
-                }
-                (KeyAction::End, _) => {
-                    self.finish_composition(ctx.as_ref())?;
-                    synthesize_key_input(wparam, lparam);
-                    true
+        self.contain(|| {
+            if self.is_forwarded(wparam.0 as _)
+                || !self.is_keyboard_open()
+                || self.is_keyboard_disabled()
+            {
+                return Ok(FALSE);
+            }
+
+            let ctx = ctx.ok()?;
+            let op = match self.key_action(wparam.0 as _) {
+                KeyAction::Pass => return Ok(FALSE),
+                action @ (KeyAction::OneShot | KeyAction::Append) => {
+                    return self.type_key(ctx, action, read_key(wparam.0 as _, true)?);
                 }
-                (KeyAction::Backspace, _) => {
-                    self.pop_char_from_composition(ctx.unwrap())?;
-                    true
+                // The key reaches the application unless the policy eats it.
+                KeyAction::End(terminator) => {
+                    return self
+                        .end_composition(Some(ctx), terminator, Some(wparam.0 as _))
+                        .map(BOOL::from);
                 }
-                (KeyAction::Cancel, _) => {
-                    self.cancel_composition(ctx.unwrap())?;
-                    true
-                }
-                (KeyAction::AppendDot, _) => {
-                    self.append_char_to_composition(ctx.unwrap(), b'.')?;
-
-                    let trailer = self.state().unwrap().transcriber.dot_trailer();
-                    self.append_char_to_composition(ctx.unwrap(), trailer)?;
-                    true
+                KeyAction::Backspace => EditOp::Backspace,
+                // The key reaches the application unless there was a word to reopen.
+                KeyAction::Reopen => return self.reopen_last_commit(ctx).map(BOOL::from),
+                // The key reaches the application unless there was something to revert.
+                KeyAction::Revert => return self.revert(ctx).map(BOOL::from),
+                KeyAction::NextCandidate => EditOp::NextCandidate,
+                KeyAction::Cancel => EditOp::Cancel,
+                KeyAction::AppendDot => {
+                    let Some(trailer) = self.with_state(|s| s.transcriber.dot_trailer()) else {
+                        return Ok(FALSE);
+                    };
+                    self.apply_ops(ctx, [EditOp::Append('.'), EditOp::Append(trailer)])?;
+                    return Ok(TRUE);
                 }
             };
 
-        Ok(is_eaten.into())
+            self.apply_ops(ctx, [op])?;
+            Ok(TRUE)
+        })
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnKeyUp(&self, _: Ref<'_, ITfContext>, _: WPARAM, _: LPARAM) -> Result<BOOL> {
-        Ok(FALSE)
+        self.contain(|| Ok(FALSE))
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnPreservedKey(&self, _: Ref<'_, ITfContext>, _: *const GUID) -> Result<BOOL> {
-        Ok(FALSE)
+        self.contain(|| Ok(FALSE))
     }
 }
 
-fn convert_vkey(code: u32) -> Result<u8> {
+impl Ime_Impl {
+    /// Sends `key` again, to reach the application past the IME, once the composition it
+    /// ended is committed.
+    #[tracing::instrument(skip(self), ret, err)]
+    pub(crate) fn forward_key(&self, key: u16) -> Result<()> {
+        let flags = if key_class::is_extended(key) {
+            KEYEVENTF_EXTENDEDKEY
+        } else {
+            KEYBD_EVENT_FLAGS(0)
+        };
+        let input = |flags| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: VIRTUAL_KEY(key),
+                    dwFlags: flags,
+                    ..Default::default()
+                },
+            },
+        };
+        let inputs = [input(flags), input(flags | KEYEVENTF_KEYUP)];
+
+        self.with_state(|s| s.forwarded_key = Some(key));
+        if unsafe { SendInput(&inputs, size_of::<INPUT>() as i32) } as usize != inputs.len() {
+            self.with_state(|s| s.forwarded_key = None);
+            return Err(Error::from_thread());
+        }
+        Ok(())
+    }
+
+    /// Whether `key` is one sent again by [`Self::forward_key`], which the IME lets through
+    /// once.
+    fn is_forwarded(&self, key: u16) -> bool {
+        self.with_state(|s| s.forwarded_key.take_if(|forwarded| *forwarded == key))
+            .flatten()
+            .is_some()
+    }
+
+    /// Types the characters of a key, once combined with a waiting dead key.
+    // #[tracing::instrument(skip(self, ctx), ret, err)]
+    fn type_key(&self, ctx: &ITfContext, action: KeyAction, input: KeyInput) -> Result<BOOL> {
+        let is_dead = matches!(input, KeyInput::Dead(_));
+        let Some(typed) = self.with_state(|s| s.dead_keys.feed(input)) else {
+            return Ok(FALSE);
+        };
+        let KeyInput::Chars(chars) = typed else {
+            // A dead key is eaten, waiting for the next key.
+            return Ok(is_dead.into());
+        };
+
+        // The shortcode prefix and escape delimiters are symbols, but have to start a
+        // composition.
+        let chars = chars.as_slice();
+        let appends = action == KeyAction::Append
+            || chars.first().is_some_and(|&ch| self.starts_composition(ch));
+        let ops = chars.iter().map(|&ch| {
+            if appends {
+                EditOp::Append(ch)
+            } else {
+                EditOp::Insert(ch)
+            }
+        });
+        self.apply_ops(ctx, ops.collect::<Vec<_>>())?;
+        Ok(TRUE)
+    }
+}
+
+/// Reads what the key types under the active layout, modifiers included.
+///
+/// A key that's `typed` goes through the dead key state of the layout, which holds a dead key
+/// and combines it with the next key typed, as it would for the application. Otherwise the key
+/// is only looked at, leaving the state alone for when it's typed.
+fn read_key(code: u32, typed: bool) -> Result<KeyInput> {
+    const DONT_CHANGE_KEYBOARD_STATE: u32 = 0x4;
+
     let scan_code = unsafe { MapVirtualKeyW(code, MAPVK_VK_TO_VSC) };
 
     let mut keyboard_state = [0u8; 256];
@@ -113,77 +280,19 @@ fn convert_vkey(code: u32) -> Result<u8> {
 
     let layout = unsafe { GetKeyboardLayout(0) };
 
-    let mut ch = 0;
+    let mut units = [0u16; 8];
     let count = unsafe {
         ToUnicodeEx(
             code,
             scan_code,
             &keyboard_state,
-            std::slice::from_mut(&mut ch),
-            0,
+            &mut units,
+            if typed { 0 } else { DONT_CHANGE_KEYBOARD_STATE },
             Some(layout),
         )
     };
 
-    tracing::trace!(ch, wch = %(ch as u8 as char));
-
-    (count == 1).then_some(ch as _).ok_or(S_FALSE.into())
-}
-
-#[tracing::instrument]
-fn synthesize_key_input(wparam: WPARAM, lparam: LPARAM) {
-    let shifted = is_active(VK_SHIFT);
-    let ctrled = is_active(VK_CONTROL);
-    let alted = is_active(VK_MENU);
-    let metaed = is_active(VK_LWIN) || is_active(VK_RWIN);
-
-    tracing::trace!(shifted, ctrled, alted, metaed);
-
-    let modifiers = [
-        (metaed, VK_LWIN),
-        (ctrled, VK_CONTROL),
-        (alted, VK_MENU),
-        (shifted, VK_SHIFT),
-    ];
-
-    let active_keys = [
-        make_input(
-            VIRTUAL_KEY(wparam.0 as _),
-            (lparam.0.cast_unsigned() >> 16) as _,
-            KEYBD_EVENT_FLAGS::default(),
-        ),
-        make_input(
-            VIRTUAL_KEY(wparam.0 as _),
-            (lparam.0.cast_unsigned() >> 16) as _,
-            KEYEVENTF_KEYUP,
-        ),
-    ];
-    let _keys =
-        modifiers
-            .into_iter()
-            .filter_map(|(is_active, key)| {
-                is_active.then(|| make_input(key, 0, KEYBD_EVENT_FLAGS::default()))
-            })
-            .chain(active_keys)
-            .chain(modifiers.into_iter().rev().filter_map(|(is_active, key)| {
-                is_active.then(|| make_input(key, 0, KEYEVENTF_KEYUP))
-            }))
-            .collect::<Vec<_>>();
-
-    unsafe { SendInput(&active_keys, std::mem::size_of::<INPUT>() as _) };
-}
-
-fn make_input(key: VIRTUAL_KEY, scan: u16, flags: KEYBD_EVENT_FLAGS) -> INPUT {
-    INPUT {
-        r#type: INPUT_KEYBOARD,
-        Anonymous: INPUT_0 {
-            ki: KEYBDINPUT {
-                wVk: key,
-                wScan: scan,
-                dwFlags: flags,
-                time: 0,
-                dwExtraInfo: SYNTH,
-            },
-        },
-    }
+    let input = KeyInput::decode(&units, count);
+    tracing::trace!(count, ?input);
+    Ok(input)
 }
@@ -0,0 +1,339 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::cell::{Cell, RefCell};
+
+use windows::Win32::{
+    Graphics::Gdi::HBITMAP,
+    System::Ole::{CONNECT_E_ADVISELIMIT, CONNECT_E_CANNOTCONNECT, CONNECT_E_NOCONNECTION},
+    UI::{Input::KeyboardAndMouse::HKL, WindowsAndMessaging::*},
+};
+
+use crate::*;
+
+const SINK_COOKIE: u32 = 0x4c42_4931;
+const MENU_ID_SEPARATOR: u32 = 0xff;
+const MENU_ID_SETTINGS: u32 = 0x100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InputMode {
+    Avro,
+    Khipro,
+    English,
+}
+
+impl InputMode {
+    const ALL: [Self; 3] = [Self::Avro, Self::Khipro, Self::English];
+
+    pub(crate) fn of(transcriber: &transcriber::Transcriber) -> Self {
+        match transcriber {
+            transcriber::Transcriber::Avro(_) => Self::Avro,
+            transcriber::Transcriber::Khipro(_) => Self::Khipro,
+        }
+    }
+
+    /// Ids of the modes in the menu start at 1, as 0 means no item was picked.
+    fn menu_id(self) -> u32 {
+        self as u32 + 1
+    }
+
+    fn from_menu_id(id: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.menu_id() == id)
+    }
+
+    fn profile(self) -> Option<GUID> {
+        match self {
+            Self::Avro => Some(globals::IME_PROFILE_AVRO),
+            Self::Khipro => Some(globals::IME_PROFILE_KHIPRO),
+            Self::English => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Avro => "অভ্র",
+            Self::Khipro => "ক্ষিপ্র",
+            Self::English => "English",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Avro => globals::IME_PROFILE_DESCRIPTION_AVRO,
+            Self::Khipro => globals::IME_PROFILE_DESCRIPTION_KHIPRO,
+            Self::English => globals::IME_DESCRIPTION_ENGLISH,
+        }
+    }
+
+    fn icon_index(self) -> u32 {
+        match self {
+            Self::Avro => globals::IME_ICON_INDEX_AVRO,
+            Self::Khipro => globals::IME_ICON_INDEX_KHIPRO,
+            Self::English => globals::IME_ICON_INDEX_ENGLISH,
+        }
+    }
+}
+
+/// The input mode button shown in the language bar and the system tray input indicator.
+#[implement(ITfLangBarItemButton, ITfLangBarItem, ITfSource)]
+#[derive(Debug)]
+pub(crate) struct LangBarItemButton {
+    thread_mgr: ITfThreadMgr,
+    client_id: u32,
+    mode: Cell<InputMode>,
+    /// Whether the IME runs on a secure desktop, like the logon screen, where the settings
+    /// mustn't be opened with the rights of the system.
+    is_secure_mode: bool,
+    sink: RefCell<Option<ITfLangBarItemSink>>,
+}
+
+impl LangBarItemButton {
+    pub(crate) fn new(
+        thread_mgr: &ITfThreadMgr,
+        client_id: u32,
+        mode: InputMode,
+        is_secure_mode: bool,
+    ) -> Self {
+        LangBarItemButton {
+            thread_mgr: thread_mgr.clone(),
+            client_id,
+            mode: mode.into(),
+            is_secure_mode,
+            sink: None.into(),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) fn set_mode(&self, mode: InputMode) {
+        if self.mode.replace(mode) == mode {
+            return;
+        }
+
+        let sink = self.sink.borrow().clone();
+        if let Some(sink) = sink
+            && let Err(e) = unsafe { sink.OnUpdate(TF_LBI_ICON | TF_LBI_TEXT | TF_LBI_TOOLTIP) }
+        {
+            tracing::warn!(%e, "failed to update language bar item");
+        }
+    }
+
+    #[tracing::instrument(skip(self), ret, err)]
+    fn switch_mode(&self, mode: InputMode) -> Result<()> {
+        if let Some(profile) = mode.profile() {
+            let profile_manager = utils::create_instance_inproc::<ITfInputProcessorProfileMgr>(
+                &CLSID_TF_InputProcessorProfiles,
+            )?;
+            unsafe {
+                profile_manager.ActivateProfile(
+                    TF_PROFILETYPE_INPUTPROCESSOR,
+                    globals::IME_LANGID,
+                    &globals::IME_CLSID,
+                    &profile,
+                    HKL::default(),
+                    TF_IPPMF_DONTCARECURRENTINPUTLANGUAGE,
+                )
+            }?;
+        }
+
+        compartment::write_bool(
+            &self.thread_mgr,
+            self.client_id,
+            GUID_COMPARTMENT_KEYBOARD_OPENCLOSE,
+            mode != InputMode::English,
+        )
+    }
+}
+
+impl ITfLangBarItem_Impl for LangBarItemButton_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetInfo(&self, info: *mut TF_LANGBARITEMINFO) -> Result<()> {
+        panic_guard::contain(|| {
+            if info.is_null() {
+                return E_INVALIDARG.ok();
+            }
+
+            let mut description = [0u16; TF_LBI_DESC_MAXLEN as usize];
+            // Leave room for the nul terminator.
+            for (dst, src) in description[..TF_LBI_DESC_MAXLEN as usize - 1]
+                .iter_mut()
+                .zip(globals::IME_DESCRIPTION.encode_utf16())
+            {
+                *dst = src;
+            }
+
+            unsafe {
+                info.write(TF_LANGBARITEMINFO {
+                    clsidService: globals::IME_CLSID,
+                    guidItem: GUID_LBI_INPUTMODE,
+                    dwStyle: TF_LBI_STYLE_BTN_BUTTON | TF_LBI_STYLE_SHOWNINTRAY,
+                    ulSort: 0,
+                    szDescription: description,
+                })
+            };
+            Ok(())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetStatus(&self) -> Result<u32> {
+        panic_guard::contain(|| Ok(0))
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn Show(&self, _: BOOL) -> Result<()> {
+        panic_guard::contain(|| E_NOTIMPL.ok())
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetTooltipString(&self) -> Result<BSTR> {
+        panic_guard::contain(|| Ok(self.mode.get().description().into()))
+    }
+}
+
+impl ITfLangBarItemButton_Impl for LangBarItemButton_Impl {
+    #[tracing::instrument(skip_all, ret, err)]
+    fn OnClick(&self, click: TfLBIClick, _: &POINT, _: *const RECT) -> Result<()> {
+        panic_guard::contain(|| {
+            if click == TF_LBI_CLK_LEFT {
+                // Toggling keeps the active profile, so the Bangla scheme is restored on reopening.
+                compartment::write_bool(
+                    &self.thread_mgr,
+                    self.client_id,
+                    GUID_COMPARTMENT_KEYBOARD_OPENCLOSE,
+                    self.mode.get() == InputMode::English,
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn InitMenu(&self, menu: Ref<'_, ITfMenu>) -> Result<()> {
+        panic_guard::contain(|| {
+            let menu = menu.ok()?;
+            let add_item = |id, flags, label: &str| {
+                let label = label.encode_utf16().collect::<Vec<_>>();
+                unsafe {
+                    menu.AddMenuItem(
+                        id,
+                        flags,
+                        HBITMAP::default(),
+                        HBITMAP::default(),
+                        &label,
+                        std::ptr::null_mut(),
+                    )
+                }
+            };
+
+            for mode in InputMode::ALL {
+                let flags = if mode == self.mode.get() {
+                    TF_LBMENUF_RADIOCHECKED
+                } else {
+                    0
+                };
+                add_item(mode.menu_id(), flags, mode.label())?;
+            }
+            if self.is_secure_mode {
+                return Ok(());
+            }
+            add_item(MENU_ID_SEPARATOR, TF_LBMENUF_SEPARATOR, "")?;
+            add_item(MENU_ID_SETTINGS, 0, "Settings")
+        })
+    }
+
+    #[tracing::instrument(skip(self), ret, err)]
+    fn OnMenuSelect(&self, id: u32) -> Result<()> {
+        panic_guard::contain(|| {
+            if id == MENU_ID_SETTINGS {
+                if self.is_secure_mode {
+                    return E_ACCESSDENIED.ok();
+                }
+                settings::open()
+            } else if let Some(mode) = InputMode::from_menu_id(id) {
+                self.switch_mode(mode)
+            } else {
+                E_INVALIDARG.ok()
+            }
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetIcon(&self) -> Result<HICON> {
+        panic_guard::contain(|| {
+            let size = unsafe { GetSystemMetrics(SM_CXSMICON) };
+            // Negative icon indices are resource identifiers, which `LoadImageW` takes as is.
+            let resource_id = self.mode.get().icon_index().cast_signed().unsigned_abs();
+            let icon = unsafe {
+                LoadImageW(
+                    Some(HINSTANCE(dll::instance_handle().0)),
+                    PCWSTR(resource_id as usize as *const u16),
+                    IMAGE_ICON,
+                    size,
+                    size,
+                    LR_DEFAULTCOLOR,
+                )
+            }?;
+            Ok(HICON(icon.0))
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn GetText(&self) -> Result<BSTR> {
+        panic_guard::contain(|| Ok(self.mode.get().label().into()))
+    }
+}
+
+impl ITfSource_Impl for LangBarItemButton_Impl {
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn AdviseSink(&self, iid: *const GUID, sink: Ref<'_, IUnknown>) -> Result<u32> {
+        panic_guard::contain(|| {
+            if unsafe { iid.as_ref() } != Some(&ITfLangBarItemSink::IID) {
+                return Err(CONNECT_E_CANNOTCONNECT.into());
+            }
+
+            let mut current = self.sink.borrow_mut();
+            if current.is_some() {
+                return Err(CONNECT_E_ADVISELIMIT.into());
+            }
+
+            *current = Some(sink.ok()?.cast()?);
+            Ok(SINK_COOKIE)
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    fn UnadviseSink(&self, cookie: u32) -> Result<()> {
+        panic_guard::contain(|| {
+            if cookie != SINK_COOKIE || self.sink.take().is_none() {
+                return CONNECT_E_NOCONNECTION.ok();
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Ime_Impl {
+    pub(crate) fn input_mode(&self) -> Option<InputMode> {
+        let (thread_mgr, mode) =
+            self.with_state(|s| (s.thread_mgr.clone(), InputMode::of(&s.transcriber)))?;
+        Some(
+            if compartment::read_bool(&thread_mgr, GUID_COMPARTMENT_KEYBOARD_OPENCLOSE) {
+                mode
+            } else {
+                InputMode::English
+            },
+        )
+    }
+
+    /// Refreshes the language bar button to reflect the current input mode.
+    pub(crate) fn update_lang_bar_item(&self) {
+        let Some(mode) = self.input_mode() else {
+            return;
+        };
+
+        let lang_bar_item = self.with_state(|s| s.lang_bar_item.clone());
+        if let Some(lang_bar_item) = lang_bar_item {
+            lang_bar_item.set_mode(mode);
+        }
+    }
+}
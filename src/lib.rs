@@ -1,8 +1,9 @@
 // Copyright 2026 Muhammad Ragib Hasin
 // SPDX-License-Identifier: MPL-2.0
 
-use std::cell::{self, RefCell};
+use std::cell::RefCell;
 
+use uo_keyboard_core::state::StateCell;
 use windows::Win32::{Foundation::*, UI::TextServices::*};
 use windows::core::*;
 
@@ -13,15 +14,22 @@ mod globals;
 
 mod dll;
 mod factory;
+mod panic_guard;
 mod registration;
 
 mod active_profile_notify_sink;
+mod alternates;
 mod compartment;
+mod display_attribute;
 mod edit_session;
 mod function_provider;
 mod key_class;
 mod key_event_sink;
+mod lang_bar_item;
 mod other_sinks;
+mod prediction;
+mod settings;
+mod sink;
 mod text_input_processor;
 
 #[implement(
@@ -32,13 +40,16 @@ mod text_input_processor;
     ITfCompositionSink,
     ITfActiveLanguageProfileNotifySink,
     ITfThreadFocusSink,
+    ITfCompartmentEventSink,
+    ITfDisplayAttributeProvider,
     ITfFunctionProvider,
     ITfFunction,
-    ITfFnGetPreferredTouchKeyboardLayout
+    ITfFnGetPreferredTouchKeyboardLayout,
+    ITfFnGetLinguisticAlternates
 )]
 #[derive(Debug)]
 struct Ime {
-    state: RefCell<Option<ActiveImeState>>,
+    state: StateCell<ActiveImeState>,
     last_focused: RefCell<Option<ITfDocumentMgr>>,
 }
 
@@ -47,17 +58,43 @@ struct ActiveImeState {
     thread_mgr: ITfThreadMgr,
     client_id: u32,
 
-    thread_mgr_event_sink_cookie: u32,
-
-    text_edit_ctx: Option<ITfContext>,
-    text_edit_sink_cookie: u32,
-
-    active_profile_notify_sink_cookie: u32,
-
-    thread_focus_sink_cookie: u32,
+    /// Sinks advised on activation, unadvised on deactivation.
+    sinks: sink::SinkStack,
+    /// Text edit sink of the focused context.
+    text_edit_sink: Option<sink::SinkGuard>,
+
+    lang_bar_item: ComObject<lang_bar_item::LangBarItemButton>,
+
+    display_attribute_atoms: display_attribute::DisplayAttributeAtoms,
+
+    settings: settings::Settings,
+    shortcodes: uo_keyboard_core::shortcode::ShortcodeTable,
+    recent_commits: uo_keyboard_core::recent::RecentCommits,
+    ngram: Option<uo_keyboard_core::ngram::NgramModel>,
+    /// Words committed last, the context of next-word predictions.
+    word_history: uo_keyboard_core::ngram::WordHistory,
+    /// Whether the edit ending next is the IME's own, set by its edit sessions writing to the
+    /// document, so that a selection change it makes doesn't count as the caret moving away.
+    is_own_edit: bool,
+    /// A key held back by the IME and sent again, which is left to the application when it
+    /// comes back.
+    forwarded_key: Option<u16>,
+    spell_checker: uo_keyboard_core::spell::SpellChecker,
 
     transcriber: transcriber::Transcriber,
+    context_seeds: uo_keyboard_core::context::SeedTable,
+    preedit: uo_keyboard_core::preedit::Preedit,
+    /// Holds a copy of the preedit text while it's set in the document.
+    text_buffer: Vec<u16>,
+    pending_ops: uo_keyboard_core::ops::OpQueue,
+    dead_keys: uo_keyboard_core::keys::DeadKeys,
     composition: Option<edit_session::Composition>,
+    /// Keys typed straight into the document, when there is no composition.
+    direct_input: uo_keyboard_core::direct::DirectInput,
+    /// Whether the application is typed into without compositions, as told in the settings.
+    uses_direct_input: bool,
+    /// The document that refused a composition, typed into without one until the focus moves.
+    refused_composition: Option<ITfDocumentMgr>,
 }
 
 impl Ime {
@@ -66,31 +103,17 @@ impl Ime {
         factory::dll_add_ref();
 
         Ok(Ime {
-            state: None.into(),
+            state: StateCell::new(),
             last_focused: None.into(),
         })
     }
 
+    /// Runs `f` on the state of an active IME, unless it's already being accessed.
+    ///
+    /// Nothing calling out to TSF may be done in `f`, as TSF may call back into the IME.
     // #[tracing::instrument]
-    fn state(&self) -> Option<cell::Ref<'_, ActiveImeState>> {
-        cell::Ref::filter_map(self.state.borrow(), Option::as_ref).ok()
-    }
-
-    // #[tracing::instrument]
-    fn state_mut(&self) -> Option<cell::RefMut<'_, ActiveImeState>> {
-        cell::RefMut::filter_map(self.state.borrow_mut(), Option::as_mut).ok()
-    }
-
-    // #[tracing::instrument]
-    fn composition(&self) -> Option<cell::Ref<'_, edit_session::Composition>> {
-        self.state()
-            .and_then(|s| cell::Ref::filter_map(s, |s| s.composition.as_ref()).ok())
-    }
-
-    // #[tracing::instrument]
-    fn composition_mut(&self) -> Option<cell::RefMut<'_, edit_session::Composition>> {
-        self.state_mut()
-            .and_then(|s| cell::RefMut::filter_map(s, |s| s.composition.as_mut()).ok())
+    fn with_state<R>(&self, f: impl FnOnce(&mut ActiveImeState) -> R) -> Option<R> {
+        self.state.with(f)
     }
 }
 
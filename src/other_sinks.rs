@@ -1,6 +1,8 @@
 // Copyright 2026 Muhammad Ragib Hasin
 // SPDX-License-Identifier: MPL-2.0
 
+use uo_keyboard_core::commit::Terminator;
+
 use crate::*;
 
 impl ITfTextEditSink_Impl for Ime_Impl {
@@ -12,52 +14,79 @@ impl ITfTextEditSink_Impl for Ime_Impl {
         edit_cookie: u32,
         edit_record: Ref<'_, ITfEditRecord>,
     ) -> Result<()> {
-        let edit_record = edit_record.ok()?;
-        if unsafe { edit_record.GetSelectionStatus() }?.as_bool() {
-            return Ok(());
-        }
-
-        let Some(range) = self
-            .composition()
-            .map(|c| unsafe { c.tf_composition.GetRange() })
-        else {
-            return Ok(());
-        };
-
-        let selection = utils::get_selection(edit_cookie, ctx.ok()?, TF_DEFAULT_SELECTION)?;
-
-        if let Some(selection_range) = selection.range
-            && !utils::is_range_covered(edit_cookie, &selection_range, &range?)
-        {
-            tracing::trace!("range clobber");
-            self.finish_composition(ctx.as_ref(), )?;
-        }
-
-        Ok(())
+        self.contain(|| {
+            let edit_record = edit_record.ok()?;
+            let is_own_edit = self
+                .with_state(|s| std::mem::take(&mut s.is_own_edit))
+                .unwrap_or(false);
+            if unsafe { edit_record.GetSelectionStatus() }?.as_bool() {
+                // The application may have typed a forwarded terminator after the commit, or
+                // the caret may have been moved away from it.
+                if !is_own_edit {
+                    self.caret_moved(ctx.ok()?, edit_cookie)?;
+                }
+                return Ok(());
+            }
+
+            let Some(tf_composition) = self
+                .with_state(|s| s.composition.as_ref().map(|c| c.tf_composition.clone()))
+                .flatten()
+            else {
+                return Ok(());
+            };
+            let range = unsafe { tf_composition.GetRange() };
+
+            let selection = utils::get_selection(edit_cookie, ctx.ok()?, TF_DEFAULT_SELECTION)?;
+
+            if let Some(selection_range) = selection.range
+                && !utils::is_range_covered(edit_cookie, &selection_range, &range?)
+            {
+                tracing::trace!("range clobber");
+                self.finish_composition(ctx.as_ref())?;
+            }
+
+            Ok(())
+        })
     }
 }
 
 impl ITfThreadFocusSink_Impl for Ime_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnSetThreadFocus(&self) -> Result<()> {
-        Ok(())
+        self.contain(|| Ok(()))
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnKillThreadFocus(&self) -> Result<()> {
-        Ok(())
+        self.contain(|| Ok(()))
+    }
+}
+
+impl ITfCompartmentEventSink_Impl for Ime_Impl {
+    #[tracing::instrument(skip_all, ret, err)]
+    fn OnChange(&self, guid: *const GUID) -> Result<()> {
+        self.contain(|| {
+            let guid = unsafe { guid.as_ref() }.ok_or(S_OK)?;
+            if *guid == GUID_COMPARTMENT_KEYBOARD_OPENCLOSE {
+                if self.input_mode() == Some(lang_bar_item::InputMode::English) {
+                    self.finish_composition(None)?;
+                }
+                self.update_lang_bar_item();
+            }
+            Ok(())
+        })
     }
 }
 
 impl ITfThreadMgrEventSink_Impl for Ime_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnInitDocumentMgr(&self, _: Ref<'_, ITfDocumentMgr>) -> Result<()> {
-        E_NOTIMPL.ok()
+        self.contain(|| E_NOTIMPL.ok())
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnUninitDocumentMgr(&self, _: Ref<'_, ITfDocumentMgr>) -> Result<()> {
-        E_NOTIMPL.ok()
+        self.contain(|| E_NOTIMPL.ok())
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
@@ -66,23 +95,37 @@ impl ITfThreadMgrEventSink_Impl for Ime_Impl {
         focus: Ref<'_, ITfDocumentMgr>,
         _prev_focus: Ref<'_, ITfDocumentMgr>,
     ) -> Result<()> {
-        let focus = focus.as_ref();
+        self.contain(|| {
+            let focus = focus.as_ref();
 
-        self.update_text_edit_sink_focus(focus)?;
+            // A composition doesn't follow the focus to another document.
+            self.end_composition(None, Terminator::FocusLoss, None)?;
 
-        self.last_focused.replace(focus.cloned());
+            self.with_state(|s| {
+                s.word_history.clear();
+                s.recent_commits.clear();
+                s.dead_keys.clear();
+                s.direct_input.clear();
+                // Another document may take a composition.
+                s.refused_composition = None;
+            });
 
-        Ok(())
+            self.update_text_edit_sink_focus(focus)?;
+
+            self.last_focused.replace(focus.cloned());
+
+            Ok(())
+        })
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnPushContext(&self, _: Ref<'_, ITfContext>) -> Result<()> {
-        E_NOTIMPL.ok()
+        self.contain(|| E_NOTIMPL.ok())
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn OnPopContext(&self, _: Ref<'_, ITfContext>) -> Result<()> {
-        E_NOTIMPL.ok()
+        self.contain(|| E_NOTIMPL.ok())
     }
 }
 
@@ -91,14 +134,12 @@ impl Ime_Impl {
     /// Always release any previous sink.
     // #[tracing::instrument(skip_all, ret, err)]
     fn update_text_edit_sink_focus(&self, doc_mgr: Option<&ITfDocumentMgr>) -> Result<()> {
-        let Some(mut state) = self.state_mut() else {
+        let Some(previous_sink) = self.with_state(|s| s.text_edit_sink.take()) else {
             return Ok(());
         };
 
-        if let Some(ctx) = state.text_edit_ctx.take() {
-            let source = ctx.cast::<ITfSource>()?;
-            unsafe { source.UnadviseSink(state.text_edit_sink_cookie) }?;
-            state.text_edit_sink_cookie = TF_INVALID_COOKIE;
+        if let Some(sink) = previous_sink {
+            sink.release()?;
         }
 
         let Some(doc_mgr) = doc_mgr else {
@@ -111,10 +152,13 @@ impl Ime_Impl {
             Err(e) => return Err(e),
         };
 
-        let source = ctx.cast::<ITfSource>()?;
-        let cookie = unsafe { source.AdviseSink(&ITfTextEditSink::IID, self.as_interface()) }?;
-        state.text_edit_sink_cookie = cookie;
-        state.text_edit_ctx.replace(ctx);
+        let sink = sink::SinkGuard::new(sink::Sink::advise_source(
+            &ctx.cast()?,
+            &ITfTextEditSink::IID,
+            self.as_interface(),
+        )?);
+        // Dropped, and so unadvised, if the IME was deactivated meanwhile.
+        self.with_state(|s| s.text_edit_sink = Some(sink));
 
         Ok(())
     }
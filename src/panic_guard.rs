@@ -0,0 +1,47 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+//! Containment of panics at the COM and DLL boundary.
+//!
+//! A panic unwinding out of a COM method or a DLL export aborts the host process, be it
+//! Explorer or a word processor, so every entry point runs its body through [`contain`]. The
+//! panic itself is logged by the `tracing_panic` hook installed in `DllMain`.
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use crate::*;
+
+/// Runs `f`, turning a panic into `E_UNEXPECTED`.
+pub(crate) fn contain<R>(f: impl FnOnce() -> Result<R>) -> Result<R> {
+    contain_or(f, || {})
+}
+
+/// Runs `f`, calling `on_panic` to restore whatever `f` left half done if it panics.
+fn contain_or<R>(f: impl FnOnce() -> Result<R>, on_panic: impl FnOnce()) -> Result<R> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| {
+        on_panic();
+        Err(E_UNEXPECTED.into())
+    })
+}
+
+/// Runs `f` of a DLL export, turning a panic into `E_UNEXPECTED`.
+pub(crate) fn contain_hresult(f: impl FnOnce() -> HRESULT) -> HRESULT {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(E_UNEXPECTED)
+}
+
+impl Ime_Impl {
+    /// Runs `f` as [`contain`] does, resetting the IME to an idle state after a panic.
+    ///
+    /// The composition is forgotten rather than ended, as its text can't be trusted.
+    pub(crate) fn contain<R>(&self, f: impl FnOnce() -> Result<R>) -> Result<R> {
+        contain_or(f, || {
+            let reset = self.with_state(|s| {
+                s.composition = None;
+                s.pending_ops.clear();
+                s.preedit.clear();
+                s.word_history.clear();
+            });
+            tracing::warn!(reset = reset.is_some(), "contained a panic");
+        })
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::*;
+
+use uo_keyboard_core::phrase::{self, PhraseCandidates};
+
+const PREDICTION_LIMIT: usize = 5;
+/// Predictions a word has to be among to count as likely after the words before it.
+const FOLLOWER_LIMIT: usize = 8;
+
+impl Ime_Impl {
+    /// Words likely to follow the ones committed last, the most likely first.
+    #[tracing::instrument(skip(self), ret)]
+    pub(crate) fn next_word_predictions(&self) -> Vec<String> {
+        self.with_state(|s| {
+            let Some(model) = &s.ngram else {
+                return Vec::new();
+            };
+            model
+                .predict(&s.word_history.words(), PREDICTION_LIMIT)
+                .into_iter()
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+}
+
+impl ActiveImeState {
+    /// Candidates for the converted text of the composition, ranked by the words committed
+    /// before it and the words around each swapped word.
+    ///
+    /// Returns `None` unless the text is the conversion of the input.
+    pub(crate) fn phrase_candidates(&self) -> Option<PhraseCandidates> {
+        self.preedit.words().next()?;
+        Some(PhraseCandidates::new(
+            self.preedit.words(),
+            &self.word_history.words(),
+            |word, roman| {
+                self.spell_checker
+                    .alternates(word, Some(roman), phrase::WORD_ALTERNATES)
+                    .into_iter()
+                    .map(|alternate| alternate.word)
+                    .collect()
+            },
+            |context, word| {
+                self.ngram
+                    .as_ref()
+                    .is_some_and(|model| model.predict(context, FOLLOWER_LIMIT).contains(&word))
+            },
+        ))
+    }
+}
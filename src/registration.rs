@@ -67,6 +67,7 @@ pub(crate) fn unregister_profile() -> Result<()> {
 
 const SUPPORT_CATEGORIES: &[GUID] = &[
     GUID_TFCAT_TIP_KEYBOARD,
+    GUID_TFCAT_DISPLAYATTRIBUTEPROVIDER,
     GUID_TFCAT_TIPCAP_UIELEMENTENABLED,
     GUID_TFCAT_TIPCAP_SECUREMODE,
     GUID_TFCAT_TIPCAP_COMLESS,
@@ -103,7 +104,8 @@ pub(crate) fn register_server(dll_instance_handle: HMODULE) -> Result<()> {
     let key = windows_registry::CLASSES_ROOT.create(ime_key())?;
     key.set_string("", globals::IME_DESCRIPTION)?;
 
-    let module_file_name = String::from_utf16(&get_module_file_name(dll_instance_handle)).unwrap();
+    let module_file_name = String::from_utf16(&get_module_file_name(dll_instance_handle))
+        .map_err(|e| Error::new(E_FAIL, e.to_string()))?;
     let inproc_key = key.create("InProcServer32")?;
     inproc_key.set_string("", module_file_name)?;
     inproc_key.set_string("ThreadingModel", "Apartment")?;
@@ -0,0 +1,305 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use std::path::PathBuf;
+
+use uo_keyboard_core::{
+    calendar::DateFormat, commit::CommitPolicy, escape::Escapes, hotkey::Hotkey,
+    phrase::CompositionMode, shortcode::ShortcodeTable,
+};
+use windows::Win32::UI::{Shell::ShellExecuteW, WindowsAndMessaging::SW_SHOWNORMAL};
+
+use crate::*;
+
+const SETTINGS_DIR: &str = "Uo Keyboard";
+const SETTINGS_FILE: &str = "settings.ini";
+const SETTINGS_TEMPLATE: &str = "\
+; Ũõ Keyboard settings
+;
+; Prefix starting an emoji or symbol shortcode like :smile: or :taka:, empty to disable.
+; More shortcodes can be added as `code = symbol` lines in shortcodes.txt next to this file.
+; Numbers are written out as shortcodes too, once closed by the prefix, like :12345: in words,
+; or with a suffix picking the form: d for grouped digits (:12345d:), o for an ordinal (:2o:),
+; t for taka (:12345.50t:) and tw for taka in words (:12345.50tw:).
+;shortcode_prefix = :
+;
+; Formats of the current date and time typed by the date shortcode, separated by |, where
+; :date: types the first, :date2: the second and so on. A format starts with bangabda:
+; to use the Bangla calendar of Bangladesh. In a format, %d is the day, %o the day with its
+; suffix like ৩রা, %m the month, %B its name, %Y the year, %A the weekday, %H the hour from
+; 00 to 23, %I the hour from 1 to 12, %M the minute, %p the part of the day like সকাল, and
+; %% a %.
+;date_formats = bangabda:%d %B %Y | %d %B %Y | %A, %d %B %Y | %p %I:%M
+;
+; Delimiters around text kept as typed within a composition, like {iPhone}, separated by a
+; space, empty to disable. Balanced pairs nest within the text.
+;escape_delimiters = { }
+;
+; What ends a composition: space, tab, enter, navigation (arrows, Home, End, Page Up and
+; Page Down), escape or focus_loss. Each of them can commit_and_forward, leaving the key to
+; the application, commit or cancel.
+;on_space = commit_and_forward
+;on_tab = commit_and_forward
+;on_enter = commit
+;on_navigation = commit_and_forward
+;on_escape = cancel
+;on_focus_loss = commit
+;
+; What a composition holds: a word, ending on a space, or a phrase, where spaces are typed
+; into it until a space after punctuation or one of the other terminators ends it.
+;composition_mode = word
+;
+; Key switching the composition to its next candidate, where one of its words is swapped for
+; a word sounding alike, empty to disable. Candidates fitting the words around them, and the
+; words committed before, come first once a model is saved as ngram.bin, see below.
+;candidate_key = ctrl+space
+;
+; Key turning the composition, or the word just committed, back into the roman text typed,
+; like ctrl+z or escape, empty to disable. It takes over from on_escape if set to escape.
+;revert_key = ctrl+z
+;
+; Applications typed into without a composition, replacing the text before the caret on each
+; key instead, as a comma separated list of executable names like notepad.exe. This is only
+; needed for applications misbehaving with compositions, as those refusing one fall back to it.
+;direct_input_apps =
+;
+; Words missing from the spell checker can be added one per line in words.txt. The bundled
+; list only holds common words, so for a full dictionary, copy the .dic file of a Hunspell
+; dictionary, like bn_BD.dic, next to this file. Its affix rules aren't applied, so only the
+; word forms it lists are known.
+;
+; Words likely to follow the ones just committed are offered as alternates at the caret once
+; a model trained by uo-ngram is saved as ngram.bin next to this file.
+";
+const DEFAULT_DATE_FORMATS: &str = "bangabda:%d %B %Y | %d %B %Y | %A, %d %B %Y | %p %I:%M";
+const SHORTCODES_FILE: &str = "shortcodes.txt";
+const USER_WORDS_FILE: &str = "words.txt";
+/// Extension of Hunspell dictionaries, loaded into the spell checker.
+const DICTIONARY_EXTENSION: &str = "dic";
+const NGRAM_FILE: &str = "ngram.bin";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Settings {
+    pub(crate) shortcode_prefix: String,
+    pub(crate) commit_policy: CommitPolicy,
+    pub(crate) revert_key: Option<Hotkey>,
+    pub(crate) candidate_key: Option<Hotkey>,
+    pub(crate) escapes: Option<Escapes>,
+    pub(crate) composition_mode: CompositionMode,
+    /// Lowercase executable names of the applications typed into without a composition.
+    pub(crate) direct_input_apps: Vec<String>,
+    /// Formats of the date shortcode, in the order of their numbers.
+    pub(crate) date_formats: Vec<DateFormat>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            shortcode_prefix: ":".to_owned(),
+            commit_policy: CommitPolicy::default(),
+            revert_key: Hotkey::parse("ctrl+z"),
+            candidate_key: Hotkey::parse("ctrl+space"),
+            escapes: Escapes::new("{", "}"),
+            composition_mode: CompositionMode::default(),
+            direct_input_apps: Vec::new(),
+            date_formats: parse_date_formats(DEFAULT_DATE_FORMATS),
+        }
+    }
+}
+
+impl Settings {
+    /// Loads the settings file, using defaults for anything missing or invalid.
+    #[tracing::instrument(ret)]
+    pub(crate) fn load() -> Self {
+        let mut settings = Settings::default();
+        if let Some(text) = settings_file().and_then(read_optional) {
+            settings.apply(&text);
+        }
+        settings
+    }
+
+    /// Whether the application the IME is loaded into is typed into without a composition.
+    pub(crate) fn uses_direct_input(&self) -> bool {
+        let Some(app) = std::env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.file_name()?.to_string_lossy().to_lowercase()))
+        else {
+            return false;
+        };
+        self.direct_input_apps.contains(&app)
+    }
+
+    /// Applies `key = value` lines over the current values.
+    fn apply(&mut self, text: &str) {
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with([';', '#']) {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                tracing::warn!(line, "malformed setting");
+                continue;
+            };
+
+            match key.trim() {
+                "shortcode_prefix" => self.shortcode_prefix = value.trim().to_owned(),
+                "composition_mode" => match CompositionMode::from_name(value.trim()) {
+                    Some(mode) => self.composition_mode = mode,
+                    None => tracing::warn!(value, "unknown composition mode"),
+                },
+                "direct_input_apps" => {
+                    self.direct_input_apps = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|app| !app.is_empty())
+                        .map(str::to_lowercase)
+                        .collect();
+                }
+                "date_formats" => self.date_formats = parse_date_formats(value),
+                "escape_delimiters" => {
+                    let mut delimiters = value.split_whitespace();
+                    match (delimiters.next(), delimiters.next(), delimiters.next()) {
+                        (None, _, _) => self.escapes = None,
+                        (Some(open), Some(close), None) => self.escapes = Escapes::new(open, close),
+                        _ => tracing::warn!(value, "malformed escape delimiters"),
+                    }
+                }
+                "revert_key" => match value.trim() {
+                    "" => self.revert_key = None,
+                    value => match Hotkey::parse(value) {
+                        Some(hotkey) => self.revert_key = Some(hotkey),
+                        None => tracing::warn!(value, "unknown revert key"),
+                    },
+                },
+                "candidate_key" => match value.trim() {
+                    "" => self.candidate_key = None,
+                    value => match Hotkey::parse(value) {
+                        Some(hotkey) => self.candidate_key = Some(hotkey),
+                        None => tracing::warn!(value, "unknown candidate key"),
+                    },
+                },
+                key => match key.strip_prefix("on_") {
+                    Some(terminator)
+                        if self.commit_policy.set_by_name(terminator, value.trim()) => {}
+                    _ => tracing::warn!(key, "unknown setting"),
+                },
+            }
+        }
+    }
+}
+
+/// Reads date formats separated by `|`, skipping empty ones.
+fn parse_date_formats(text: &str) -> Vec<DateFormat> {
+    text.split('|')
+        .map(DateFormat::parse)
+        .filter(|format| !format.pattern.is_empty())
+        .collect()
+}
+
+/// Builds the shortcode table from the defaults and the user's `shortcodes.txt`.
+#[tracing::instrument]
+pub(crate) fn load_shortcodes() -> ShortcodeTable {
+    let mut table = ShortcodeTable::default();
+    if let Some(text) = settings_dir()
+        .map(|dir| dir.join(SHORTCODES_FILE))
+        .and_then(read_optional)
+    {
+        let malformed = table.extend_from_str(&text);
+        if !malformed.is_empty() {
+            tracing::warn!(?malformed, "malformed shortcode lines");
+        }
+    }
+    table
+}
+
+/// Builds the spell checker from the bundled word list, the user's `words.txt` and the
+/// Hunspell dictionaries in the settings directory.
+#[tracing::instrument(skip_all)]
+pub(crate) fn load_spell_checker() -> uo_keyboard_core::spell::SpellChecker {
+    let mut checker = uo_keyboard_core::spell::SpellChecker::bundled();
+    let Some(dir) = settings_dir() else {
+        return checker;
+    };
+
+    if let Some(text) = read_optional(dir.join(USER_WORDS_FILE)) {
+        checker.extend_from_str(&text);
+    }
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return checker,
+        Err(e) => {
+            tracing::warn!(?dir, %e, "failed to list dictionaries");
+            return checker;
+        }
+    };
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        let is_dictionary = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(DICTIONARY_EXTENSION));
+        if let Some(text) = is_dictionary.then(|| read_optional(path)).flatten() {
+            checker.extend_from_dic(&text);
+        }
+    }
+    checker
+}
+
+/// Loads the next-word prediction model trained by `uo-ngram`, if the user has one.
+#[tracing::instrument]
+pub(crate) fn load_ngram() -> Option<uo_keyboard_core::ngram::NgramModel> {
+    let path = settings_dir()?.join(NGRAM_FILE);
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => {
+            tracing::warn!(?path, %e, "failed to read n-gram model");
+            return None;
+        }
+    };
+
+    uo_keyboard_core::ngram::NgramModel::from_bytes(&bytes)
+        .inspect_err(|e| tracing::warn!(?path, %e, "invalid n-gram model"))
+        .ok()
+}
+
+fn read_optional(path: PathBuf) -> Option<String> {
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Some(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            tracing::warn!(?path, %e, "failed to read user file");
+            None
+        }
+    }
+}
+
+/// Per-user directory holding the settings file and user data, under `%APPDATA%`.
+pub(crate) fn settings_dir() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(|app_data| PathBuf::from(app_data).join(SETTINGS_DIR))
+}
+
+pub(crate) fn settings_file() -> Option<PathBuf> {
+    settings_dir().map(|dir| dir.join(SETTINGS_FILE))
+}
+
+/// Opens the settings file in the default editor, creating it first if needed.
+#[tracing::instrument(ret, err)]
+pub(crate) fn open() -> Result<()> {
+    let file = settings_file().ok_or(E_UNEXPECTED)?;
+
+    if !file.exists() {
+        std::fs::create_dir_all(file.parent().unwrap_or(&file))
+            .and_then(|_| std::fs::write(&file, SETTINGS_TEMPLATE))
+            .map_err(|e| Error::new(E_FAIL, e.to_string()))?;
+    }
+
+    let file = HSTRING::from(file.as_path());
+    let instance = unsafe { ShellExecuteW(None, w!("open"), &file, None, None, SW_SHOWNORMAL) };
+
+    // `ShellExecuteW` signals success with a value greater than 32.
+    if instance.0 as usize > 32 {
+        Ok(())
+    } else {
+        E_FAIL.ok()
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2026 Muhammad Ragib Hasin
+// SPDX-License-Identifier: MPL-2.0
+
+use uo_keyboard_core::sink::Advised;
+
+use crate::*;
+
+pub(crate) type SinkGuard = uo_keyboard_core::sink::SinkGuard<Sink>;
+pub(crate) type SinkStack = uo_keyboard_core::sink::SinkStack<Sink>;
+
+/// A sink of the IME advised to TSF.
+#[derive(Debug)]
+pub(crate) enum Sink {
+    Source {
+        source: ITfSource,
+        cookie: u32,
+    },
+    SingleSource {
+        source: ITfSourceSingle,
+        client_id: u32,
+        iid: GUID,
+    },
+    KeyEvent {
+        keystroke_mgr: ITfKeystrokeMgr,
+        client_id: u32,
+    },
+    Compartment {
+        thread_mgr: ITfThreadMgr,
+        guid: GUID,
+        cookie: u32,
+    },
+    LangBarItem {
+        lang_bar_item_mgr: ITfLangBarItemMgr,
+        item: ITfLangBarItem,
+    },
+}
+
+impl Sink {
+    // #[tracing::instrument(skip_all, ret, err)]
+    pub(crate) fn advise_source(
+        source: &ITfSource,
+        iid: &GUID,
+        sink: impl Param<IUnknown>,
+    ) -> Result<Self> {
+        let cookie = unsafe { source.AdviseSink(iid, sink) }?;
+        Ok(Sink::Source {
+            source: source.clone(),
+            cookie,
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    pub(crate) fn advise_single_source(
+        source: &ITfSourceSingle,
+        client_id: u32,
+        iid: &GUID,
+        sink: impl Param<IUnknown>,
+    ) -> Result<Self> {
+        unsafe { source.AdviseSingleSink(client_id, iid, sink) }?;
+        Ok(Sink::SingleSource {
+            source: source.clone(),
+            client_id,
+            iid: *iid,
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    pub(crate) fn advise_key_event(
+        keystroke_mgr: &ITfKeystrokeMgr,
+        client_id: u32,
+        sink: &ITfKeyEventSink,
+    ) -> Result<Self> {
+        unsafe { keystroke_mgr.AdviseKeyEventSink(client_id, sink, true) }?;
+        Ok(Sink::KeyEvent {
+            keystroke_mgr: keystroke_mgr.clone(),
+            client_id,
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    pub(crate) fn advise_compartment(
+        thread_mgr: &ITfThreadMgr,
+        guid: GUID,
+        sink: &IUnknown,
+    ) -> Result<Self> {
+        let cookie = compartment::advise_sink(thread_mgr, guid, sink)?;
+        Ok(Sink::Compartment {
+            thread_mgr: thread_mgr.clone(),
+            guid,
+            cookie,
+        })
+    }
+
+    // #[tracing::instrument(skip_all, ret, err)]
+    pub(crate) fn add_lang_bar_item(
+        lang_bar_item_mgr: &ITfLangBarItemMgr,
+        item: &ITfLangBarItem,
+    ) -> Result<Self> {
+        unsafe { lang_bar_item_mgr.AddItem(item) }?;
+        Ok(Sink::LangBarItem {
+            lang_bar_item_mgr: lang_bar_item_mgr.clone(),
+            item: item.clone(),
+        })
+    }
+}
+
+impl Advised for Sink {
+    type Error = Error;
+
+    #[tracing::instrument(ret, err)]
+    fn unadvise(&self) -> Result<()> {
+        match self {
+            Sink::Source { source, cookie } => unsafe { source.UnadviseSink(*cookie) },
+            Sink::SingleSource {
+                source,
+                client_id,
+                iid,
+            } => unsafe { source.UnadviseSingleSink(*client_id, iid) },
+            Sink::KeyEvent {
+                keystroke_mgr,
+                client_id,
+            } => unsafe { keystroke_mgr.UnadviseKeyEventSink(*client_id) },
+            Sink::Compartment {
+                thread_mgr,
+                guid,
+                cookie,
+            } => compartment::unadvise_sink(thread_mgr, *guid, *cookie),
+            Sink::LangBarItem {
+                lang_bar_item_mgr,
+                item,
+            } => unsafe { lang_bar_item_mgr.RemoveItem(item) },
+        }
+    }
+}
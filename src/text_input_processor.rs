@@ -23,46 +23,114 @@ impl Ime_Impl {
         let thread_mgr = thread_mgr.ok()?.clone();
         let source = thread_mgr.cast::<ITfSource>()?;
 
-        let thread_mgr_event_sink_cookie =
-            unsafe { source.AdviseSink(&ITfThreadMgrEventSink::IID, self.as_interface()) }?;
+        // Whatever is advised is unadvised when `sinks` drops on a failure.
+        let mut sinks = sink::SinkStack::new();
+
+        sinks.push(sink::Sink::advise_source(
+            &source,
+            &ITfThreadMgrEventSink::IID,
+            self.as_interface(),
+        )?);
 
         let text_edit_ctx = unsafe { thread_mgr.GetFocus()?.GetTop() }?;
-        let text_edit_source = text_edit_ctx.cast::<ITfSource>()?;
-        let text_edit_sink_cookie =
-            unsafe { text_edit_source.AdviseSink(&ITfTextEditSink::IID, self.as_interface()) }?;
+        let text_edit_sink = sink::SinkGuard::new(sink::Sink::advise_source(
+            &text_edit_ctx.cast()?,
+            &ITfTextEditSink::IID,
+            self.as_interface(),
+        )?);
 
         let keystroke_mgr = thread_mgr.cast::<ITfKeystrokeMgr>()?;
-        unsafe { keystroke_mgr.AdviseKeyEventSink(client_id, self.as_interface(), true) }?;
-
-        let active_profile_notify_sink_cookie = unsafe {
-            source.AdviseSink(
-                &ITfActiveLanguageProfileNotifySink::IID,
-                self.as_interface(),
-            )
-        }?;
-
-        let thread_focus_sink_cookie =
-            unsafe { source.AdviseSink(&ITfThreadFocusSink::IID, self.as_interface()) }?;
-
-        let single_source = thread_mgr.cast::<ITfSourceSingle>()?;
-        unsafe {
-            single_source.AdviseSingleSink(
-                client_id,
-                &ITfFunctionProvider::IID,
-                self.as_interface(),
-            )
-        }?;
+        sinks.push(sink::Sink::advise_key_event(
+            &keystroke_mgr,
+            client_id,
+            &self.as_interface(),
+        )?);
+
+        sinks.push(sink::Sink::advise_source(
+            &source,
+            &ITfActiveLanguageProfileNotifySink::IID,
+            self.as_interface(),
+        )?);
+
+        sinks.push(sink::Sink::advise_source(
+            &source,
+            &ITfThreadFocusSink::IID,
+            self.as_interface(),
+        )?);
+
+        sinks.push(sink::Sink::advise_single_source(
+            &thread_mgr.cast()?,
+            client_id,
+            &ITfFunctionProvider::IID,
+            self.as_interface(),
+        )?);
+
+        // Keeps the keyboard closed if it was switched to English before this activation.
+        compartment::init_bool(
+            &thread_mgr,
+            client_id,
+            GUID_COMPARTMENT_KEYBOARD_OPENCLOSE,
+            true,
+        )?;
+        sinks.push(sink::Sink::advise_compartment(
+            &thread_mgr,
+            GUID_COMPARTMENT_KEYBOARD_OPENCLOSE,
+            &self.as_interface(),
+        )?);
+
+        // On the logon screen and other secure desktops, nothing may be launched from the IME.
+        let is_secure_mode = thread_mgr
+            .cast::<ITfThreadMgrEx>()
+            .and_then(|thread_mgr| unsafe { thread_mgr.GetActiveFlags() })
+            .is_ok_and(|flags| flags & TF_TMF_SECUREMODE != 0);
+        let lang_bar_item = lang_bar_item::LangBarItemButton::new(
+            &thread_mgr,
+            client_id,
+            lang_bar_item::InputMode::of(&transcriber),
+            is_secure_mode,
+        )
+        .into_object();
+        sinks.push(sink::Sink::add_lang_bar_item(
+            &thread_mgr.cast()?,
+            &lang_bar_item.as_interface(),
+        )?);
+
+        let display_attribute_atoms = display_attribute::DisplayAttributeAtoms::register()?;
+
+        let settings = settings::Settings::load();
+        let mut preedit = uo_keyboard_core::preedit::Preedit::new();
+        preedit.set_escapes(settings.escapes.clone());
+        let mut direct_input = uo_keyboard_core::direct::DirectInput::new();
+        direct_input.set_escapes(settings.escapes.clone());
+        let uses_direct_input = settings.uses_direct_input();
+        let mut shortcodes = settings::load_shortcodes();
+        shortcodes.set_date_formats(settings.date_formats.clone());
 
         Ok(ActiveImeState {
             thread_mgr,
             client_id,
-            thread_mgr_event_sink_cookie,
-            text_edit_ctx: Some(text_edit_ctx),
-            text_edit_sink_cookie,
-            active_profile_notify_sink_cookie,
-            thread_focus_sink_cookie,
+            sinks,
+            text_edit_sink: Some(text_edit_sink),
+            lang_bar_item,
+            display_attribute_atoms,
+            settings,
+            shortcodes,
+            recent_commits: Default::default(),
+            ngram: settings::load_ngram(),
+            word_history: Default::default(),
+            is_own_edit: false,
+            forwarded_key: None,
+            spell_checker: settings::load_spell_checker(),
+            context_seeds: transcriber.context_seeds(),
             transcriber,
+            preedit,
+            text_buffer: Vec::new(),
+            pending_ops: Default::default(),
+            dead_keys: Default::default(),
             composition: None,
+            direct_input,
+            uses_direct_input,
+            refused_composition: None,
         })
     }
 }
@@ -70,63 +138,45 @@ impl Ime_Impl {
 impl ActiveImeState {
     #[tracing::instrument(skip_all, err)]
     fn destroy(self) -> Result<()> {
-        let Self {
-            thread_mgr,
-            client_id,
-            thread_mgr_event_sink_cookie,
-            active_profile_notify_sink_cookie,
-            thread_focus_sink_cookie,
-            ..
-        } = self;
-
-        let single_source = thread_mgr.cast::<ITfSourceSingle>()?;
-        unsafe { single_source.UnadviseSingleSink(client_id, &ITfFunctionProvider::IID) }?;
-
-        let source = thread_mgr.cast::<ITfSource>()?;
-
-        unsafe { source.UnadviseSink(thread_focus_sink_cookie) }?;
-        unsafe { source.UnadviseSink(active_profile_notify_sink_cookie) }?;
-
-        let keystroke_mgr = thread_mgr.cast::<ITfKeystrokeMgr>()?;
-        unsafe { keystroke_mgr.UnadviseKeyEventSink(client_id) }?;
-
-        unsafe { source.UnadviseSink(thread_mgr_event_sink_cookie) }?;
-
-        Ok(())
+        let text_edit_sink = self.text_edit_sink.map_or(Ok(()), |sink| sink.release());
+        self.sinks.release().and(text_edit_sink)
     }
 }
 
 impl ITfTextInputProcessor_Impl for Ime_Impl {
     fn Activate(&self, _: Ref<'_, ITfThreadMgr>, _: u32) -> Result<()> {
-        E_NOTIMPL.ok()
+        self.contain(|| E_NOTIMPL.ok())
     }
 
     // #[tracing::instrument(skip_all, ret, err)]
     fn Deactivate(&self) -> Result<()> {
-        tracing::trace!("deactivate ime");
-        self.finish_composition(None)?;
+        self.contain(|| {
+            tracing::trace!("deactivate ime");
+            self.finish_composition(None)?;
 
-        if let Some(state) = self.state.take() {
-            state.destroy()?;
-        }
+            if let Some(state) = self.state.take() {
+                state.destroy()?;
+            }
 
-        Ok(())
+            Ok(())
+        })
     }
 }
 
 impl ITfTextInputProcessorEx_Impl for Ime_Impl {
     // #[tracing::instrument(skip_all, ret, err)]
     fn ActivateEx(&self, thread_mgr: Ref<'_, ITfThreadMgr>, client_id: u32, _: u32) -> Result<()> {
-        tracing::trace!("ITfTextInputProcessorEx::ActivateEx IME");
-        if let Ok(state) = self.activate(thread_mgr, client_id) {
-            self.state.replace(Some(state));
-            Ok(())
-        } else {
-            Err(if let Err(e) = self.Deactivate() {
-                e
-            } else {
-                E_FAIL.into()
-            })
-        }
+        self.contain(|| {
+            tracing::trace!("ITfTextInputProcessorEx::ActivateEx IME");
+            // A failed activation has unadvised whatever it advised.
+            let state = self.activate(thread_mgr, client_id)?;
+            match self.state.set(state) {
+                Ok(()) => Ok(()),
+                Err(state) => {
+                    state.destroy()?;
+                    E_UNEXPECTED.ok()
+                }
+            }
+        })
     }
 }
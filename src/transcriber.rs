@@ -1,6 +1,8 @@
 // Copyright 2026 Muhammad Ragib Hasin
 // SPDX-License-Identifier: MPL-2.0
 
+use uo_keyboard_core::{context, escape::Escapes, keys};
+
 use crate::*;
 
 pub(crate) enum Transcriber {
@@ -18,38 +20,66 @@ impl std::fmt::Debug for Transcriber {
 }
 
 impl Transcriber {
+    /// The transcriber of `profile`, falling back to Avro for a profile it doesn't know.
     pub(crate) fn new(profile: GUID) -> Self {
         match profile {
             globals::IME_PROFILE_AVRO => Self::Avro(okkhor::parser::Parser::new_phonetic()),
             globals::IME_PROFILE_KHIPRO => Self::Khipro(okkhor::khipro::KhiproPhonetic::new()),
-            _ => panic!("Transcriber only supports Avro or Khipro profiles"),
+            _ => {
+                tracing::warn!(?profile, "unknown profile, falling back to Avro");
+                Self::Avro(okkhor::parser::Parser::new_phonetic())
+            }
         }
     }
 
-    pub(crate) fn convert(&self, raw_input: &str) -> String {
-        let mut output = String::with_capacity(64);
-        self.convert_into(raw_input, &mut output);
-        output
+    /// Transcribes `raw_input`, passing characters other than ASCII through unchanged.
+    pub(crate) fn convert_into(&self, raw_input: &str, output: &mut String) {
+        keys::transcribe_ascii(raw_input, output, |input, output| match self {
+            Transcriber::Avro(scribe) => scribe.convert_into(input, output),
+            Transcriber::Khipro(scribe) => scribe.convert_into(input, output),
+        })
     }
 
-    pub(crate) fn convert_into(&self, raw_input: &str, output: &mut String) {
+    /// How each consonant is typed, to continue words before a new composition.
+    pub(crate) fn context_seeds(&self) -> context::SeedTable {
+        context::SeedTable::build(|input, output| self.convert_into(input, output))
+    }
+
+    pub(crate) fn adapt_char(&self, ch: char) -> char {
         match self {
-            Transcriber::Avro(scribe) => scribe.convert_into(raw_input, output),
-            Transcriber::Khipro(scribe) => scribe.convert_into(raw_input, output),
+            Transcriber::Avro(_) => ch,
+            Transcriber::Khipro(_) => ch.to_ascii_lowercase(),
         }
     }
 
-    pub(crate) fn adapt_char(&self, ch: u8) -> char {
-        match self {
-            Transcriber::Avro(_) => ch as _,
-            Transcriber::Khipro(_) => ch.to_ascii_lowercase() as _,
+    /// A key typed after `input`, adapted unless it's within a literal segment.
+    pub(crate) fn adapt_key(&self, input: &str, ch: char, escapes: Option<&Escapes>) -> char {
+        if escapes.is_some_and(|escapes| escapes.is_open(input)) {
+            ch
+        } else {
+            self.adapt_char(ch)
+        }
+    }
+
+    /// Appends a typed key to a composition input, adapted unless it's within a literal
+    /// segment.
+    pub(crate) fn push_adapted(&self, input: &mut String, ch: char, escapes: Option<&Escapes>) {
+        input.push(self.adapt_key(input, ch, escapes));
+    }
+
+    /// The composition input for keys typed as `typed`.
+    pub(crate) fn adapt_input(&self, typed: &str, escapes: Option<&Escapes>) -> String {
+        let mut input = String::new();
+        for ch in typed.chars() {
+            self.push_adapted(&mut input, ch, escapes);
         }
+        input
     }
 
-    pub(crate) fn dot_trailer(&self) -> u8 {
+    pub(crate) fn dot_trailer(&self) -> char {
         match self {
-            Transcriber::Avro(_) => b'`',
-            Transcriber::Khipro(_) => b'.',
+            Transcriber::Avro(_) => '`',
+            Transcriber::Khipro(_) => '.',
         }
     }
 }
@@ -3,6 +3,9 @@
 
 use std::{mem::ManuallyDrop, slice};
 
+use uo_keyboard_core::calendar::{Date, Time};
+use windows::Win32::System::SystemInformation::GetLocalTime;
+
 use crate::*;
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -70,3 +73,18 @@ pub(crate) fn create_instance_inproc<T: Interface>(clsid: &GUID) -> Result<T> {
     use windows::Win32::System::Com::{CLSCTX_INPROC_SERVER, CoCreateInstance};
     unsafe { CoCreateInstance(clsid, None, CLSCTX_INPROC_SERVER) }
 }
+
+/// The local date and time of the system.
+pub(crate) fn local_now() -> (Date, Time) {
+    let now = unsafe { GetLocalTime() };
+    let date = Date {
+        year: now.wYear.into(),
+        month: now.wMonth as u8,
+        day: now.wDay as u8,
+    };
+    let time = Time {
+        hour: now.wHour as u8,
+        minute: now.wMinute as u8,
+    };
+    (date, time)
+}